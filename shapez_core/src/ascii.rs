@@ -0,0 +1,216 @@
+use crate::{Color, QUADS_AMOUNT, Quad, Shape};
+use std::fmt;
+
+/// The quads of a layer as they are laid out in a 2x2 grid, row by row.
+const GRID: [[usize; 2]; 2] = [[3, 0], [2, 1]];
+
+/// Separates the grids of two layers.
+const SEPARATOR: &str = " | ";
+
+const EMPTY: &str = "--";
+/// A quad of a layer only the other side of a diff has, or a quad both sides agree on.
+const MISSING: &str = "  ";
+const CHANGED: &str = "^^";
+
+impl Color {
+    /// Returns the ANSI escape code of the terminal color closest to this color.
+    fn ansi_code(self) -> u8 {
+        match self {
+            Self::Red => 31,
+            Self::Green => 32,
+            Self::Yellow => 33,
+            Self::Blue => 34,
+            Self::Purple => 35,
+            Self::Cyan => 36,
+            Self::White => 97,
+            Self::Uncolored => 90,
+        }
+    }
+}
+
+impl Shape {
+    /// Draws every layer of the shape as a 2x2 grid of quads, from the bottom layer on the left
+    /// to the top layer on the right.
+    ///
+    /// Each quad is written as in a short key, with the top left quad in the top left corner of its grid.
+    /// A shape without layers is drawn as an empty string.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use shapez_core::Shape;
+    ///
+    /// let shape: Shape = "RuCw--Cw:----Ru--".parse().unwrap();
+    /// assert_eq!(shape.to_ascii(), "Cw Ru | -- --\n-- Cw | Ru --");
+    /// ```
+    pub fn to_ascii(&self) -> String {
+        let cells = self.layers.iter().map(|layer| layer.map(ascii_cell));
+        draw(&cells.collect::<Vec<_>>()).join("\n")
+    }
+
+    /// Same as [`Shape::to_ascii`], but coloring each quad with ANSI escape codes for terminals.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use shapez_core::Shape;
+    ///
+    /// let shape: Shape = "Cr------".parse().unwrap();
+    /// assert_eq!(shape.to_ansi(), "-- \x1b[31mCr\x1b[0m\n-- --");
+    /// ```
+    pub fn to_ansi(&self) -> String {
+        let cells = self.layers.iter().map(|layer| {
+            layer.map(|quad| match quad {
+                Some(Quad(_, color)) => {
+                    format!("\x1b[{}m{}\x1b[0m", color.ansi_code(), ascii_cell(quad))
+                }
+                None => ascii_cell(quad),
+            })
+        });
+        draw(&cells.collect::<Vec<_>>()).join("\n")
+    }
+
+    /// Draws both shapes like [`Shape::to_ascii`], followed by a grid marking every quad they differ in.
+    ///
+    /// Layers only one of the shapes has are compared against empty layers.
+    /// This is what [`assert_shape_eq!`](crate::assert_shape_eq) shows when the shapes differ.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use shapez_core::Shape;
+    ///
+    /// let left: Shape = "RuCw--Cw:----Ru--".parse().unwrap();
+    /// let right: Shape = "RuCr--Cw".parse().unwrap();
+    /// assert_eq!(
+    ///     left.ascii_diff(&right),
+    ///     [
+    ///         " left: Cw Ru | -- --",
+    ///         "       -- Cw | Ru --",
+    ///         "right: Cw Ru |",
+    ///         "       -- Cr |",
+    ///         " diff:       |",
+    ///         "          ^^ | ^^",
+    ///     ]
+    ///     .join("\n")
+    /// );
+    /// ```
+    pub fn ascii_diff(&self, other: &Shape) -> String {
+        let height = self.layers.len().max(other.layers.len());
+        let layer = |shape: &Shape, index: usize| shape.layers.get(index).copied();
+        let side = |shape: &Shape| {
+            let cells = (0..height).map(|index| match layer(shape, index) {
+                Some(layer) => layer.map(ascii_cell),
+                None => [MISSING; QUADS_AMOUNT].map(str::to_string),
+            });
+            draw(&cells.collect::<Vec<_>>())
+        };
+
+        let changes = (0..height).map(|index| {
+            let quads = |shape| layer(shape, index).unwrap_or([None; QUADS_AMOUNT]);
+            let (left, right) = (quads(self), quads(other));
+            std::array::from_fn(|quad| match left[quad] == right[quad] {
+                true => MISSING.to_string(),
+                false => CHANGED.to_string(),
+            })
+        });
+        let diff = draw(&changes.collect::<Vec<_>>());
+
+        [
+            (" left:", side(self)),
+            ("right:", side(other)),
+            (" diff:", diff),
+        ]
+        .into_iter()
+        .flat_map(|(label, rows)| {
+            rows.into_iter().enumerate().map(move |(row, line)| {
+                let label = if row == 0 { label } else { "" };
+                format!("{label:>6} {line}").trim_end().to_string()
+            })
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+    }
+}
+
+fn ascii_cell(quad: Option<Quad>) -> String {
+    match quad {
+        Some(Quad(sub_shape, color)) => format!("{}{}", sub_shape.short_key(), color.short_key()),
+        None => EMPTY.to_string(),
+    }
+}
+
+/// Draws the two rows of the grids of the layers side by side, given the cell of each quad.
+///
+/// Without any layers there is nothing to draw, so there are no rows either.
+fn draw(layers: &[[String; QUADS_AMOUNT]]) -> Vec<String> {
+    if layers.is_empty() {
+        return vec![];
+    }
+
+    GRID.map(|row| {
+        layers
+            .iter()
+            .map(|cells| row.map(|quad| cells[quad].as_str()).join(" "))
+            .collect::<Vec<_>>()
+            .join(SEPARATOR)
+    })
+    .into()
+}
+
+/// Asserts that two shapes are equal, like [`assert_eq!`],
+/// but showing both shapes as 2x2 grids with the differing quads marked when they are not.
+///
+/// Both sides can be a [`Shape`] or a [`StaticShape`](crate::StaticShape),
+/// see [`Shape::ascii_diff`] for the output.
+///
+/// # Example
+///
+/// ```
+/// use shapez_core::{Shape, assert_shape_eq};
+///
+/// let shape: Shape = "RuCw--Cw".parse().unwrap();
+/// assert_shape_eq!(shape.rotate_180().rotate_180(), shape);
+/// assert_shape_eq!(shape.rotate_cw(), shape.rotate_ccw().rotate_180(), "rotating {}", shape);
+/// ```
+#[macro_export]
+macro_rules! assert_shape_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if !(*left == *right) {
+                    $crate::assert_shape_failed(left, right, ::core::option::Option::None);
+                }
+            }
+        }
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if !(*left == *right) {
+                    $crate::assert_shape_failed(
+                        left,
+                        right,
+                        ::core::option::Option::Some(::core::format_args!($($arg)+)),
+                    );
+                }
+            }
+        }
+    };
+}
+
+/// The failure of [`assert_shape_eq!`], kept out of the macro so it expands to less code.
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_shape_failed(
+    left: &(impl Clone + Into<Shape>),
+    right: &(impl Clone + Into<Shape>),
+    message: Option<fmt::Arguments<'_>>,
+) -> ! {
+    let (left, right): (Shape, Shape) = (left.clone().into(), right.clone().into());
+    let message = message.map_or(String::new(), |message| format!(": {message}"));
+    panic!(
+        "assertion `left == right` failed{message}\n  left: {left}\n right: {right}\n{}",
+        left.ascii_diff(&right)
+    );
+}
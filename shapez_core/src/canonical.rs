@@ -0,0 +1,55 @@
+use crate::{QUADS_AMOUNT, Shape};
+
+impl Shape {
+    /// Drops the empty layers at the top of the shape.
+    ///
+    /// Short keys never contain empty layers,
+    /// but shapes built by hand or by other tools can end with some.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use shapez_core::{Color, Quad, Shape, Subshape};
+    ///
+    /// let shape = Shape {
+    ///     layers: vec![
+    ///         [Some(Quad(Subshape::Circle, Color::Red)), None, None, None],
+    ///         [None; 4],
+    ///     ],
+    /// };
+    /// assert_eq!(shape.normalize().to_string(), "Cr------");
+    /// ```
+    pub fn normalize(&self) -> Shape {
+        let height = self
+            .layers
+            .iter()
+            .rposition(|layer| layer.iter().any(Option::is_some))
+            .map_or(0, |top| top + 1);
+
+        Shape {
+            layers: self.layers[..height].to_vec(),
+        }
+    }
+
+    /// Returns the rotation of the shape with the lexicographically smallest short key.
+    ///
+    /// Shapes that are rotations of each other share the same canonical rotation,
+    /// so it can be used to compare or deduplicate shapes regardless of their rotation.
+    /// Empty top layers are kept, use [`Shape::normalize`] first to drop them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use shapez_core::Shape;
+    ///
+    /// let shape: Shape = "--RuCw--:Cu------".parse().unwrap();
+    /// assert_eq!(shape.canonical_rotation().to_string(), "----RuCw:--Cu----");
+    /// assert_eq!(shape.rotate_cw().canonical_rotation(), shape.canonical_rotation());
+    /// ```
+    pub fn canonical_rotation(&self) -> Shape {
+        std::iter::successors(Some(self.clone()), |shape| Some(shape.rotate_cw()))
+            .take(QUADS_AMOUNT)
+            .min_by_key(Shape::to_short_key)
+            .unwrap_or_else(|| self.clone())
+    }
+}
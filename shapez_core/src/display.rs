@@ -0,0 +1,86 @@
+use crate::{Color, Layer, Quad, Shape, StaticShape, Subshape};
+use std::fmt;
+
+impl Subshape {
+    /// Returns the character representing this sub-shape in a short key.
+    pub fn short_key(self) -> char {
+        match self {
+            Self::Circle => 'C',
+            Self::Square => 'S',
+            Self::Rectangle => 'R',
+            Self::Windmill => 'W',
+        }
+    }
+}
+
+impl Color {
+    /// Returns the character representing this color in a short key.
+    pub fn short_key(self) -> char {
+        match self {
+            Self::Red => 'r',
+            Self::Green => 'g',
+            Self::Blue => 'b',
+            Self::Yellow => 'y',
+            Self::Purple => 'p',
+            Self::Cyan => 'c',
+            Self::White => 'w',
+            Self::Uncolored => 'u',
+        }
+    }
+}
+
+impl Shape {
+    /// Returns the canonical short key of this shape, like `"RuCw--Cw:----Ru--"`.
+    ///
+    /// This is the exact inverse of [`parse_short_key`](crate::parse_short_key).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use shapez_core::Shape;
+    ///
+    /// let shape: Shape = "RuCw--Cw:----Ru--".parse().unwrap();
+    /// assert_eq!(shape.to_short_key(), "RuCw--Cw:----Ru--");
+    /// ```
+    pub fn to_short_key(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl StaticShape {
+    /// Returns the canonical short key of this shape, like `"RuCw--Cw:----Ru--"`.
+    pub fn to_short_key(&self) -> String {
+        self.to_string()
+    }
+}
+
+fn fmt_layers(layers: &[Layer], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (index, layer) in layers.iter().enumerate() {
+        if index > 0 {
+            f.write_str(":")?;
+        }
+
+        for quad in layer {
+            match quad {
+                Some(Quad(sub_shape, color)) => {
+                    write!(f, "{}{}", sub_shape.short_key(), color.short_key())?
+                }
+                None => f.write_str("--")?,
+            }
+        }
+    }
+
+    Ok(())
+}
+
+impl fmt::Display for Shape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_layers(&self.layers, f)
+    }
+}
+
+impl fmt::Display for StaticShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_layers(self.layers, f)
+    }
+}
@@ -0,0 +1,145 @@
+//! Runtime types for the game [shapez](https://shapez.io).
+//!
+//! These are the types the `shapez_shape!` macro from `shapez_macro` expands to.
+//! The macro always emits fully qualified paths into this crate,
+//! so no glob imports are needed at the call site.
+//!
+//! # Example
+//!
+//! ```
+//! use shapez_core::{Color, Quad, Shape, Subshape};
+//!
+//! let shape = Shape {
+//!     layers: vec![[Some(Quad(Subshape::Circle, Color::Red)), None, None, None]],
+//! };
+//! assert_eq!(shape.layers.len(), 1);
+//! ```
+
+mod ascii;
+mod canonical;
+mod display;
+mod ops;
+mod parse;
+mod recipe;
+#[cfg(feature = "render")]
+pub mod render;
+#[cfg(feature = "serde")]
+mod serialize;
+pub mod shapez2;
+mod symmetry;
+
+#[doc(hidden)]
+pub use ascii::assert_shape_failed;
+pub use parse::{
+    ShapeKeyError, parse_short_key, validate_short_key, validate_short_key_with_max_layers,
+};
+pub use recipe::{Recipe, RecipeError, find_recipe, validate_buildable_short_key};
+#[cfg(feature = "serde")]
+pub use serialize::structured;
+pub use symmetry::{Axis, equivalent_up_to_rotation};
+
+/// The maximum amount of layers a shape can have by default.
+pub const MAX_LAYERS: usize = 4;
+
+/// The amount of quads in a single layer.
+pub const QUADS_AMOUNT: usize = 4;
+
+/// A single layer of a shape.
+///
+/// Quads are ordered clockwise, starting from the top right one.
+/// An empty quad is represented by `None`.
+pub type Layer = [Option<Quad>; QUADS_AMOUNT];
+
+/// A shape, made of layers stacked from bottom to top.
+///
+/// Short keys are limited to [`MAX_LAYERS`] layers unless configured otherwise.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Shape {
+    pub layers: Vec<Layer>,
+}
+
+/// A shape whose layers live in static memory, so it can be used in `const` and `static` items.
+///
+/// This is what the `shapez_shape_const!` macro from `shapez_macro` expands to.
+///
+/// # Example
+///
+/// ```
+/// use shapez_core::{Color, Quad, Shape, StaticShape, Subshape};
+///
+/// const GOAL: StaticShape = StaticShape {
+///     layers: &[[Some(Quad(Subshape::Circle, Color::Red)), None, None, None]],
+/// };
+///
+/// let shape: Shape = GOAL.to_shape();
+/// assert_eq!(GOAL, shape);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StaticShape {
+    pub layers: &'static [Layer],
+}
+
+impl StaticShape {
+    /// Copies the layers into an owned [`Shape`].
+    pub fn to_shape(&self) -> Shape {
+        Shape {
+            layers: self.layers.to_vec(),
+        }
+    }
+}
+
+impl From<StaticShape> for Shape {
+    fn from(shape: StaticShape) -> Self {
+        shape.to_shape()
+    }
+}
+
+impl PartialEq<Shape> for StaticShape {
+    fn eq(&self, other: &Shape) -> bool {
+        self.layers == other.layers.as_slice()
+    }
+}
+
+impl PartialEq<StaticShape> for Shape {
+    fn eq(&self, other: &StaticShape) -> bool {
+        other == self
+    }
+}
+
+/// A single quad of a layer, collecting a sub-shape and a color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Quad(pub Subshape, pub Color);
+
+/// The sub-shape of a quad.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Subshape {
+    /// `C` in a short key
+    Circle,
+    /// `S` in a short key
+    Square,
+    /// `R` in a short key
+    Rectangle,
+    /// `W` in a short key
+    Windmill,
+}
+
+/// The color of a quad.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    /// `r` in a short key
+    Red,
+    /// `g` in a short key
+    Green,
+    /// `b` in a short key
+    Blue,
+    /// `y` in a short key
+    Yellow,
+    /// `p` in a short key
+    Purple,
+    /// `c` in a short key
+    Cyan,
+    /// `w` in a short key
+    White,
+    /// `u` in a short key
+    Uncolored,
+}
@@ -0,0 +1,302 @@
+use crate::{Color, Layer, MAX_LAYERS, QUADS_AMOUNT, Quad, Shape};
+
+impl Shape {
+    /// Rotates every layer of the shape clockwise by one quad.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use shapez_core::Shape;
+    ///
+    /// let shape: Shape = "RuCw--Cr".parse().unwrap();
+    /// assert_eq!(shape.rotate_cw().to_string(), "CrRuCw--");
+    /// ```
+    pub fn rotate_cw(&self) -> Shape {
+        self.map_layers(|layer| layer.rotate_right(1))
+    }
+
+    /// Rotates every layer of the shape counter-clockwise by one quad.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use shapez_core::Shape;
+    ///
+    /// let shape: Shape = "RuCw--Cr".parse().unwrap();
+    /// assert_eq!(shape.rotate_ccw().to_string(), "Cw--CrRu");
+    /// ```
+    pub fn rotate_ccw(&self) -> Shape {
+        self.map_layers(|layer| layer.rotate_left(1))
+    }
+
+    /// Rotates every layer of the shape by two quads.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use shapez_core::Shape;
+    ///
+    /// let shape: Shape = "RuCw--Cr".parse().unwrap();
+    /// assert_eq!(shape.rotate_180().to_string(), "--CrRuCw");
+    /// ```
+    pub fn rotate_180(&self) -> Shape {
+        self.map_layers(|layer| layer.rotate_left(2))
+    }
+
+    /// Cuts the shape into its west and east halves, like the cutter of shapez.
+    ///
+    /// The west half keeps quads 3 and 4, the east half quads 1 and 2.
+    /// Layers that end up empty are dropped, so a half can be empty altogether.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use shapez_core::Shape;
+    ///
+    /// let shape: Shape = "RuCw--Cr:Cu------".parse().unwrap();
+    /// let (west, east) = shape.cut();
+    /// assert_eq!(west.to_string(), "------Cr");
+    /// assert_eq!(east.to_string(), "RuCw----:Cu------");
+    /// ```
+    pub fn cut(&self) -> (Shape, Shape) {
+        let west = self.keep_quads(|index| index >= 2);
+        let east = self.keep_quads(|index| index < 2);
+        (west, east)
+    }
+
+    /// Cuts the shape into its four quads, like the quad cutter of shapez.
+    ///
+    /// The quads are returned in the order of a layer, starting from the top right one.
+    /// Layers that end up empty are dropped, so a quad can be empty altogether.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use shapez_core::Shape;
+    ///
+    /// let shape: Shape = "RuCw--Cr:Cu------".parse().unwrap();
+    /// let [first, second, third, fourth] = shape.quad_cut();
+    /// assert_eq!(first.to_string(), "Ru------:Cu------");
+    /// assert_eq!(second.to_string(), "--Cw----");
+    /// assert!(third.is_empty());
+    /// assert_eq!(fourth.to_string(), "------Cr");
+    /// ```
+    pub fn quad_cut(&self) -> [Shape; QUADS_AMOUNT] {
+        std::array::from_fn(|quad| self.keep_quads(|index| index == quad))
+    }
+
+    /// Destroys the west half of the shape, like the half-destroyer of shapez.
+    ///
+    /// This is the east half of [`Shape::cut`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use shapez_core::Shape;
+    ///
+    /// let shape: Shape = "RuCw--Cr:----Cu--".parse().unwrap();
+    /// assert_eq!(shape.destroy_half().to_string(), "RuCw----");
+    /// ```
+    pub fn destroy_half(&self) -> Shape {
+        self.keep_quads(|index| index < 2)
+    }
+
+    /// Stacks `top` onto the shape, like the stacker of shapez.
+    ///
+    /// The top shape falls down as a whole until one of its quads rests on a quad of the shape,
+    /// or until it reaches the ground.
+    /// Layers above [`MAX_LAYERS`] are cut off, see [`Shape::stack_with_max_layers`] for another limit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use shapez_core::Shape;
+    ///
+    /// let bottom: Shape = "CuCu----".parse().unwrap();
+    /// let top: Shape = "----RuRu".parse().unwrap();
+    /// assert_eq!(bottom.stack(&top).to_string(), "CuCuRuRu");
+    ///
+    /// let top: Shape = "--RuRu--".parse().unwrap();
+    /// assert_eq!(bottom.stack(&top).to_string(), "CuCu----:--RuRu--");
+    /// ```
+    pub fn stack(&self, top: &Shape) -> Shape {
+        self.stack_with_max_layers(top, MAX_LAYERS)
+    }
+
+    /// Same as [`Shape::stack`], but cutting off the layers above `max_layers` instead of [`MAX_LAYERS`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use shapez_core::Shape;
+    ///
+    /// let bottom: Shape = "CuCuCuCu:CuCuCuCu".parse().unwrap();
+    /// let top: Shape = "RuRuRuRu:RuRuRuRu".parse().unwrap();
+    /// assert_eq!(bottom.stack_with_max_layers(&top, 3).to_string(), "CuCuCuCu:CuCuCuCu:RuRuRuRu");
+    /// ```
+    pub fn stack_with_max_layers(&self, top: &Shape, max_layers: usize) -> Shape {
+        // Find the lowest layer the top shape can fall to without overlapping the shape
+        let mut merge_at = 0;
+        for (index, top_layer) in top.layers.iter().enumerate() {
+            for (quad, top_quad) in top_layer.iter().enumerate() {
+                if top_quad.is_none() {
+                    continue;
+                }
+                let resting_on = self.layers.iter().rposition(|layer| layer[quad].is_some());
+                if let Some(resting_on) = resting_on {
+                    merge_at = merge_at.max((resting_on + 1).saturating_sub(index));
+                }
+            }
+        }
+
+        let mut layers = self.layers.clone();
+        layers.resize(
+            layers.len().max(merge_at + top.layers.len()),
+            [None; QUADS_AMOUNT],
+        );
+        for (index, top_layer) in top.layers.iter().enumerate() {
+            let layer = &mut layers[merge_at + index];
+            for (quad, top_quad) in top_layer.iter().enumerate() {
+                if top_quad.is_some() {
+                    layer[quad] = *top_quad;
+                }
+            }
+        }
+        layers.truncate(max_layers);
+
+        Shape { layers }
+    }
+
+    /// Paints every quad of the top layer with `color`, leaving the layers below untouched.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use shapez_core::{Color, Shape};
+    ///
+    /// let shape: Shape = "CuCu----:--Ru--Ru".parse().unwrap();
+    /// assert_eq!(shape.paint_top(Color::Red).to_string(), "CuCu----:--Rr--Rr");
+    /// ```
+    pub fn paint_top(&self, color: Color) -> Shape {
+        let mut shape = self.clone();
+        if let Some(layer) = shape.layers.last_mut() {
+            paint_layer(layer, [Some(color); QUADS_AMOUNT]);
+        }
+
+        shape
+    }
+
+    /// Paints every quad of every layer with `color`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use shapez_core::{Color, Shape};
+    ///
+    /// let shape: Shape = "CuCu----:--Ru--Ru".parse().unwrap();
+    /// assert_eq!(shape.paint(Color::Red).to_string(), "CrCr----:--Rr--Rr");
+    /// ```
+    pub fn paint(&self, color: Color) -> Shape {
+        self.paint_quads([Some(color); QUADS_AMOUNT])
+    }
+
+    /// Paints every quad of every layer with the color of its position in `colors`,
+    /// like the quad painter of shapez.
+    ///
+    /// Quads whose color is `None` keep their color.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use shapez_core::{Color, Shape};
+    ///
+    /// let shape: Shape = "CuCuCuCu:Cu------".parse().unwrap();
+    /// let colors = [Some(Color::Red), Some(Color::Green), None, Some(Color::Blue)];
+    /// assert_eq!(shape.paint_quads(colors).to_string(), "CrCgCuCb:Cr------");
+    /// ```
+    pub fn paint_quads(&self, colors: [Option<Color>; QUADS_AMOUNT]) -> Shape {
+        self.map_layers(|layer| paint_layer(layer, colors))
+    }
+
+    /// Returns whether the shape has no layers at all,
+    /// which only happens as the result of an operation.
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Keeps the quads whose index matches `keep`, dropping layers that end up empty.
+    fn keep_quads(&self, keep: impl Fn(usize) -> bool) -> Shape {
+        let layers = self
+            .layers
+            .iter()
+            .map(|layer| std::array::from_fn(|index| layer[index].filter(|_| keep(index))))
+            .filter(|layer: &Layer| layer.iter().any(Option::is_some))
+            .collect();
+
+        Shape { layers }
+    }
+
+    fn map_layers(&self, map: impl Fn(&mut [Option<Quad>])) -> Shape {
+        let mut layers = self.layers.clone();
+        for layer in &mut layers {
+            map(layer);
+        }
+
+        Shape { layers }
+    }
+}
+
+impl Color {
+    /// Mixes two colors, like the color mixer of shapez.
+    ///
+    /// Colors mix like light: red, green and blue combine into yellow, purple, cyan and white.
+    /// Mixing with white always results in white, mixing with uncolored keeps the other color.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use shapez_core::Color;
+    ///
+    /// assert_eq!(Color::Red.mix(Color::Green), Color::Yellow);
+    /// assert_eq!(Color::Yellow.mix(Color::Purple), Color::White);
+    /// assert_eq!(Color::Blue.mix(Color::Uncolored), Color::Blue);
+    /// ```
+    pub fn mix(self, other: Color) -> Color {
+        Color::from_rgb(self.rgb() | other.rgb())
+    }
+
+    /// Returns the red, green and blue components of the color as bits.
+    fn rgb(self) -> u8 {
+        match self {
+            Self::Uncolored => 0b000,
+            Self::Red => 0b100,
+            Self::Green => 0b010,
+            Self::Blue => 0b001,
+            Self::Yellow => 0b110,
+            Self::Purple => 0b101,
+            Self::Cyan => 0b011,
+            Self::White => 0b111,
+        }
+    }
+
+    fn from_rgb(rgb: u8) -> Color {
+        match rgb {
+            0b100 => Self::Red,
+            0b010 => Self::Green,
+            0b001 => Self::Blue,
+            0b110 => Self::Yellow,
+            0b101 => Self::Purple,
+            0b011 => Self::Cyan,
+            0b111 => Self::White,
+            _ => Self::Uncolored,
+        }
+    }
+}
+
+fn paint_layer(layer: &mut [Option<Quad>], colors: [Option<Color>; QUADS_AMOUNT]) {
+    for (quad, color) in layer.iter_mut().zip(colors) {
+        if let (Some(Quad(_, old)), Some(color)) = (quad, color) {
+            *old = color;
+        }
+    }
+}
@@ -0,0 +1,444 @@
+use crate::{Color, MAX_LAYERS, Quad, Shape, Subshape};
+use std::fmt;
+use std::ops::Range;
+use std::str::FromStr;
+
+/// An error found while parsing a short key.
+///
+/// Layers and quads are counted from 1, the same way they are displayed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShapeKeyError {
+    /// The key is shorter than a single layer of `expected` characters.
+    TooShort { expected: usize },
+    /// The key contains more than `max` layers, [`MAX_LAYERS`] by default.
+    TooManyLayers { count: usize, max: usize },
+    /// A layer does not contain exactly `expected` characters,
+    /// two for each of its quads.
+    ///
+    /// `parts` names what the layers of the dialect are made of, `"quads"` or `"parts"` in shapez 2.
+    InvalidLayer {
+        layer: usize,
+        expected: usize,
+        parts: &'static str,
+    },
+    /// Every quad of a layer is empty.
+    EmptyLayer { layer: usize },
+    /// A quad contains an invalid sub-shape character.
+    ///
+    /// `expected` lists the valid characters, separated by spaces.
+    InvalidSubShape {
+        layer: usize,
+        quad: usize,
+        found: char,
+        expected: &'static str,
+    },
+    /// A quad contains an invalid color character.
+    ///
+    /// `expected` lists the valid characters, separated by spaces.
+    InvalidColor {
+        layer: usize,
+        quad: usize,
+        found: char,
+        expected: &'static str,
+    },
+    /// A quad rests on nothing, and no combination of cuts and stacks holds it in place.
+    ///
+    /// Only reported by [`validate_buildable_short_key`](crate::validate_buildable_short_key).
+    UnsupportedQuad { layer: usize, quad: usize },
+    /// A shapez 2 pin is followed by the color `found`, while pins are always written as `P-`.
+    ColoredPin {
+        layer: usize,
+        quad: usize,
+        found: char,
+    },
+}
+
+impl fmt::Display for ShapeKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooShort { expected } => write!(
+                f,
+                "key is shorter than a single layer ({expected} characters)"
+            ),
+            Self::TooManyLayers { count, max } => {
+                write!(f, "key has {count} layers, at most {max} are allowed")
+            }
+            Self::InvalidLayer {
+                layer,
+                expected,
+                parts,
+            } => write!(
+                f,
+                "layer {layer}: expected {} {parts} ({expected} characters)",
+                expected / 2
+            ),
+            Self::EmptyLayer { layer } => write!(f, "layer {layer}: every quad is empty"),
+            Self::InvalidSubShape {
+                layer,
+                quad,
+                found,
+                expected,
+            } => write!(
+                f,
+                "layer {layer}, quad {quad}: '{found}' is not a valid sub-shape (expected one of {expected})"
+            ),
+            Self::InvalidColor {
+                layer,
+                quad,
+                found,
+                expected,
+            } => write!(
+                f,
+                "layer {layer}, quad {quad}: '{found}' is not a valid color (expected one of {expected})"
+            ),
+            Self::UnsupportedQuad { layer, quad } => write!(
+                f,
+                "layer {layer}, quad {quad}: nothing is under this quad, and no combination of cuts and stacks holds it in place"
+            ),
+            Self::ColoredPin { layer, quad, found } => write!(
+                f,
+                "layer {layer}, quad {quad}: pins have no color, write `P-` instead of `P{found}`"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ShapeKeyError {}
+
+impl ShapeKeyError {
+    /// Returns the byte range of `key` this error points at.
+    ///
+    /// `key` has to be the key this error was produced from.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use shapez_core::parse_short_key;
+    ///
+    /// let key = "RuCw--Cw:--Cx----";
+    /// let err = parse_short_key(key).unwrap_err();
+    /// assert_eq!(&key[err.span(key)], "x");
+    /// assert_eq!(err.column(key), 13);
+    /// ```
+    pub fn span(&self, key: &str) -> Range<usize> {
+        match *self {
+            Self::TooShort { .. } => 0..key.len(),
+            Self::TooManyLayers { max, .. } => layer_span(key, max + 1).start..key.len(),
+            Self::InvalidLayer { layer, .. } | Self::EmptyLayer { layer } => layer_span(key, layer),
+            Self::InvalidSubShape { layer, quad, .. } => char_span(key, layer, (quad - 1) * 2),
+            Self::InvalidColor { layer, quad, .. } | Self::ColoredPin { layer, quad, .. } => {
+                char_span(key, layer, (quad - 1) * 2 + 1)
+            }
+            Self::UnsupportedQuad { layer, quad } => {
+                let sub_shape = char_span(key, layer, (quad - 1) * 2);
+                sub_shape.start..char_span(key, layer, (quad - 1) * 2 + 1).end
+            }
+        }
+    }
+
+    /// Returns the 1-based column of `key` this error starts at.
+    pub fn column(&self, key: &str) -> usize {
+        key[..self.span(key).start].chars().count() + 1
+    }
+}
+
+fn layer_span(key: &str, layer: usize) -> Range<usize> {
+    let mut start = 0;
+    for (index, input) in key.split(':').enumerate() {
+        if index + 1 == layer {
+            return start..start + input.len();
+        }
+        start += input.len() + 1;
+    }
+
+    key.len()..key.len()
+}
+
+fn char_span(key: &str, layer: usize, index: usize) -> Range<usize> {
+    let layer = layer_span(key, layer);
+    match key[layer.clone()].char_indices().nth(index) {
+        Some((offset, c)) => layer.start + offset..layer.start + offset + c.len_utf8(),
+        None => layer.end..layer.end,
+    }
+}
+
+const SUB_SHAPES: &str = "C S R W";
+const COLORS: &str = "r g b y p c w u";
+
+fn get_sub_shape(sub_shape: char) -> Option<Subshape> {
+    match sub_shape {
+        'C' => Some(Subshape::Circle),
+        'S' => Some(Subshape::Square),
+        'R' => Some(Subshape::Rectangle),
+        'W' => Some(Subshape::Windmill),
+        _ => None,
+    }
+}
+
+fn get_color(color: char) -> Option<Color> {
+    match color {
+        'r' => Some(Color::Red),
+        'g' => Some(Color::Green),
+        'b' => Some(Color::Blue),
+        'y' => Some(Color::Yellow),
+        'p' => Some(Color::Purple),
+        'c' => Some(Color::Cyan),
+        'w' => Some(Color::White),
+        'u' => Some(Color::Uncolored),
+        _ => None,
+    }
+}
+
+impl Color {
+    /// The characters of every color in a short key, separated by spaces.
+    pub const SHORT_KEYS: &'static str = COLORS;
+
+    /// Returns the color represented by `color` in a short key,
+    /// the inverse of [`Color::short_key`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use shapez_core::Color;
+    ///
+    /// assert_eq!(Color::from_short_key('p'), Some(Color::Purple));
+    /// assert_eq!(Color::from_short_key('x'), None);
+    /// ```
+    pub fn from_short_key(color: char) -> Option<Color> {
+        get_color(color)
+    }
+}
+
+fn check_quad(
+    layer: usize,
+    quad: usize,
+    chars: [char; 2],
+    errors: &mut Vec<ShapeKeyError>,
+) -> Option<Quad> {
+    let [sub_shape, color] = chars;
+
+    // Check for "--"
+    if sub_shape == '-' && color == '-' {
+        return None;
+    }
+
+    // Ensure the sub-shape and color are valid, reporting both if neither is
+    let sub_shape = get_sub_shape(sub_shape).or_else(|| {
+        errors.push(ShapeKeyError::InvalidSubShape {
+            layer,
+            quad,
+            found: sub_shape,
+            expected: SUB_SHAPES,
+        });
+        None
+    });
+    let color = get_color(color).or_else(|| {
+        errors.push(ShapeKeyError::InvalidColor {
+            layer,
+            quad,
+            found: color,
+            expected: COLORS,
+        });
+        None
+    });
+
+    Some(Quad(sub_shape?, color?))
+}
+
+/// Checks a single quad, returning `None` for empty or invalid quads.
+pub(crate) type CheckQuad<T> = fn(usize, usize, [char; 2], &mut Vec<ShapeKeyError>) -> Option<T>;
+
+fn check_layer<T: Copy, const QUADS: usize>(
+    layer: usize,
+    input: &str,
+    parts: &'static str,
+    check_quad: CheckQuad<T>,
+    errors: &mut Vec<ShapeKeyError>,
+) -> [Option<T>; QUADS] {
+    let mut quads = [None; QUADS];
+
+    // Ensure the layer is valid
+    let chars = input.chars().collect::<Vec<char>>();
+    if chars.len() != QUADS * 2 {
+        errors.push(ShapeKeyError::InvalidLayer {
+            layer,
+            expected: QUADS * 2,
+            parts,
+        });
+        return quads;
+    }
+
+    // Check every quad
+    let error_count = errors.len();
+    for (index, pair) in chars.chunks(2).enumerate() {
+        quads[index] = check_quad(layer, index + 1, [pair[0], pair[1]], errors);
+    }
+
+    // Invalid quads are not empty ones, so only check untouched layers
+    if errors.len() == error_count && quads.iter().all(Option::is_none) {
+        errors.push(ShapeKeyError::EmptyLayer { layer });
+    }
+
+    quads
+}
+
+/// Checks the layer structure of a key with `QUADS` quads per layer and up to `max_layers` layers,
+/// leaving the quads themselves to `check_quad`.
+///
+/// Shared by every short-key dialect, `parts` names what the layers of the dialect are made of.
+pub(crate) fn check_key<T: Copy, const QUADS: usize>(
+    key: &str,
+    max_layers: usize,
+    parts: &'static str,
+    check_quad: CheckQuad<T>,
+) -> Result<Vec<[Option<T>; QUADS]>, Vec<ShapeKeyError>> {
+    // Ensure the input is valid
+    if key.chars().count() < QUADS * 2 {
+        return Err(vec![ShapeKeyError::TooShort {
+            expected: QUADS * 2,
+        }]);
+    }
+
+    let mut errors = vec![];
+
+    let inputs = key.split(':').collect::<Vec<&str>>();
+    let layers = check_layers(&inputs, max_layers, parts, check_quad, &mut errors);
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(layers)
+}
+
+/// Checks each layer of a key, already split at the `:` separating them.
+fn check_layers<T: Copy, const QUADS: usize>(
+    inputs: &[impl AsRef<str>],
+    max_layers: usize,
+    parts: &'static str,
+    check_quad: CheckQuad<T>,
+    errors: &mut Vec<ShapeKeyError>,
+) -> Vec<[Option<T>; QUADS]> {
+    let mut layers = Vec::with_capacity(inputs.len());
+    for (index, layer) in inputs.iter().enumerate() {
+        // Ensure the layer count is valid, reported at the first extra layer to keep the errors in order
+        if index == max_layers {
+            errors.push(ShapeKeyError::TooManyLayers {
+                count: inputs.len(),
+                max: max_layers,
+            });
+        }
+
+        layers.push(check_layer(
+            index + 1,
+            layer.as_ref(),
+            parts,
+            check_quad,
+            errors,
+        ));
+    }
+    layers
+}
+
+/// Checks the layers of a short key, each given without the `:` separating it from the others.
+#[cfg(feature = "serde")]
+pub(crate) fn check_short_layers(
+    inputs: &[String],
+    errors: &mut Vec<ShapeKeyError>,
+) -> Vec<crate::Layer> {
+    check_layers(inputs, MAX_LAYERS, "quads", check_quad, errors)
+}
+
+/// Parses a short-form shape key like `"RuCw--Cw:----Ru--"` into a [`Shape`],
+/// collecting every problem of the key instead of stopping at the first one.
+///
+/// This is the validator `shapez_shape!` runs at compile time.
+/// Errors are ordered by their position in the key.
+///
+/// # Example
+///
+/// ```
+/// use shapez_core::{validate_short_key, ShapeKeyError};
+///
+/// assert_eq!(
+///     validate_short_key("RxCw--Cw:--------"),
+///     Err(vec![
+///         ShapeKeyError::InvalidColor {
+///             layer: 1,
+///             quad: 1,
+///             found: 'x',
+///             expected: "r g b y p c w u",
+///         },
+///         ShapeKeyError::EmptyLayer { layer: 2 },
+///     ])
+/// );
+/// ```
+pub fn validate_short_key(key: &str) -> Result<Shape, Vec<ShapeKeyError>> {
+    validate_short_key_with_max_layers(key, MAX_LAYERS)
+}
+
+/// Same as [`validate_short_key`], but accepting up to `max_layers` layers instead of [`MAX_LAYERS`].
+///
+/// # Example
+///
+/// ```
+/// use shapez_core::{validate_short_key_with_max_layers, ShapeKeyError};
+///
+/// let key = "Cu------:Cu------:Cu------:Cu------:Cu------";
+/// assert!(validate_short_key_with_max_layers(key, 5).is_ok());
+/// assert_eq!(
+///     validate_short_key_with_max_layers(key, 3),
+///     Err(vec![ShapeKeyError::TooManyLayers { count: 5, max: 3 }])
+/// );
+///
+/// // The extra layers are reported where they start, after the problems of the layers before them
+/// let errors = validate_short_key_with_max_layers("--------:Cu------:Cu------", 2).unwrap_err();
+/// assert_eq!(
+///     errors,
+///     [
+///         ShapeKeyError::EmptyLayer { layer: 1 },
+///         ShapeKeyError::TooManyLayers { count: 3, max: 2 },
+///     ]
+/// );
+/// ```
+pub fn validate_short_key_with_max_layers(
+    key: &str,
+    max_layers: usize,
+) -> Result<Shape, Vec<ShapeKeyError>> {
+    let layers = check_key(key, max_layers, "quads", check_quad)?;
+    Ok(Shape { layers })
+}
+
+/// Parses a short-form shape key like `"RuCw--Cw:----Ru--"` into a [`Shape`].
+///
+/// This is the same grammar `shapez_shape!` validates at compile time,
+/// stopping at the first problem of the key.
+/// Use [`validate_short_key`] to get every problem at once.
+///
+/// # Example
+///
+/// ```
+/// use shapez_core::{parse_short_key, ShapeKeyError};
+///
+/// assert!(parse_short_key("RuCw--Cw:----Ru--").is_ok());
+/// assert_eq!(
+///     parse_short_key("RuCx--Cw"),
+///     Err(ShapeKeyError::InvalidColor {
+///         layer: 1,
+///         quad: 2,
+///         found: 'x',
+///         expected: "r g b y p c w u",
+///     })
+/// );
+/// ```
+pub fn parse_short_key(key: &str) -> Result<Shape, ShapeKeyError> {
+    validate_short_key(key).map_err(|mut errors| errors.swap_remove(0))
+}
+
+impl FromStr for Shape {
+    type Err = ShapeKeyError;
+
+    fn from_str(key: &str) -> Result<Self, Self::Err> {
+        parse_short_key(key)
+    }
+}
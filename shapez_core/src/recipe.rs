@@ -0,0 +1,549 @@
+use crate::{
+    Color, Layer, MAX_LAYERS, QUADS_AMOUNT, Quad, Shape, ShapeKeyError, Subshape,
+    validate_short_key,
+};
+use std::fmt;
+
+/// A tree of operations building a shape from the basic shapes of the extractors.
+///
+/// The leaves are the uncolored basics (`"CuCuCuCu"`, `"RuRuRuRu"`, `"SuSuSuSu"` and `"WuWuWuWu"`),
+/// every other node applies an operation to the shapes built by its children.
+///
+/// A recipe displays in the syntax of the `shapez_expr!` macro from `shapez_macro`,
+/// e.g. `stack("CuCuCuCu", paint("RuRuRuRu", r))`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Recipe {
+    /// A basic shape, every quad holding the uncolored sub-shape
+    Extract(Subshape),
+    /// Rotates the shape clockwise, see [`Shape::rotate_cw`]
+    RotateCw(Box<Recipe>),
+    /// Rotates the shape counter-clockwise, see [`Shape::rotate_ccw`]
+    RotateCcw(Box<Recipe>),
+    /// Rotates the shape by two quads, see [`Shape::rotate_180`]
+    Rotate180(Box<Recipe>),
+    /// Keeps the west half of the cutter, see [`Shape::cut`]
+    CutWest(Box<Recipe>),
+    /// Keeps the east half of the cutter, see [`Shape::cut`]
+    CutEast(Box<Recipe>),
+    /// Stacks the second shape onto the first one, see [`Shape::stack`]
+    Stack(Box<Recipe>, Box<Recipe>),
+    /// Paints the whole shape, see [`Shape::paint`]
+    Paint(Box<Recipe>, Color),
+}
+
+impl Recipe {
+    /// Runs the operations, returning the shape they build.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use shapez_core::{Color, Recipe, Subshape};
+    ///
+    /// let recipe = Recipe::Paint(Box::new(Recipe::Extract(Subshape::Circle)), Color::Red);
+    /// assert_eq!(recipe.evaluate().to_string(), "CrCrCrCr");
+    /// ```
+    pub fn evaluate(&self) -> Shape {
+        match self {
+            Self::Extract(sub_shape) => Shape {
+                layers: vec![[Some(Quad(*sub_shape, Color::Uncolored)); QUADS_AMOUNT]],
+            },
+            Self::RotateCw(recipe) => recipe.evaluate().rotate_cw(),
+            Self::RotateCcw(recipe) => recipe.evaluate().rotate_ccw(),
+            Self::Rotate180(recipe) => recipe.evaluate().rotate_180(),
+            Self::CutWest(recipe) => recipe.evaluate().cut().0,
+            Self::CutEast(recipe) => recipe.evaluate().cut().1,
+            Self::Stack(bottom, top) => bottom.evaluate().stack(&top.evaluate()),
+            Self::Paint(recipe, color) => recipe.evaluate().paint(*color),
+        }
+    }
+
+    /// Rotates the built shape clockwise by `steps` quads.
+    fn rotated(self, steps: usize) -> Recipe {
+        match steps % QUADS_AMOUNT {
+            0 => self,
+            1 => Self::RotateCw(Box::new(self)),
+            2 => Self::Rotate180(Box::new(self)),
+            _ => Self::RotateCcw(Box::new(self)),
+        }
+    }
+
+    fn stack(self, top: Recipe) -> Recipe {
+        Self::Stack(Box::new(self), Box::new(top))
+    }
+}
+
+impl fmt::Display for Recipe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Extract(sub_shape) => {
+                let quad = format!("{}u", sub_shape.short_key());
+                write!(f, "\"{}\"", quad.repeat(QUADS_AMOUNT))
+            }
+            Self::RotateCw(recipe) => write!(f, "rotate_cw({recipe})"),
+            Self::RotateCcw(recipe) => write!(f, "rotate_ccw({recipe})"),
+            Self::Rotate180(recipe) => write!(f, "rotate_180({recipe})"),
+            Self::CutWest(recipe) => write!(f, "cut_west({recipe})"),
+            Self::CutEast(recipe) => write!(f, "cut_east({recipe})"),
+            Self::Stack(bottom, top) => write!(f, "stack({bottom}, {top})"),
+            Self::Paint(recipe, color) => write!(f, "paint({recipe}, {})", color.short_key()),
+        }
+    }
+}
+
+/// A reason a shape can not be built from the basic shapes.
+///
+/// Layers are counted from 1, the same way they are displayed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecipeError {
+    /// The shape has no layers at all.
+    EmptyShape,
+    /// Every quad of a layer is empty, which no operation leaves behind.
+    EmptyLayer { layer: usize },
+    /// The shape has more than [`MAX_LAYERS`] layers, which the stacker cuts off.
+    TooManyLayers { count: usize, max: usize },
+    /// The listed layers do not rest on a quad of the layer below them,
+    /// and no combination of cuts and stacks holds them in place.
+    Floating { layers: Vec<usize> },
+}
+
+impl fmt::Display for RecipeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyShape => write!(f, "the shape has no layers"),
+            Self::EmptyLayer { layer } => write!(f, "layer {layer}: every quad is empty"),
+            Self::TooManyLayers { count, max } => write!(
+                f,
+                "the shape has {count} layers, but the stacker cuts off every layer above {max}"
+            ),
+            Self::Floating { layers } => match layers.as_slice() {
+                [layer] => write!(
+                    f,
+                    "layer {layer} does not rest on the layer below it, and no combination of cuts and stacks holds it in place"
+                ),
+                [rest @ .., last] => {
+                    let rest = rest.iter().map(usize::to_string).collect::<Vec<_>>();
+                    write!(
+                        f,
+                        "layers {} and {last} do not rest on the layer below them, and no combination of cuts and stacks holds them in place",
+                        rest.join(", ")
+                    )
+                }
+                [] => write!(f, "no combination of cuts and stacks builds the shape"),
+            },
+        }
+    }
+}
+
+impl std::error::Error for RecipeError {}
+
+/// Searches a recipe building `target` from the basic shapes,
+/// using the cutter, the rotators, the stacker and the painter.
+///
+/// Quads can be built one by one, so the search only looks at which quads are filled.
+/// Every layer resting on the layer below can be stacked right onto it,
+/// other layers have to be held up by a half of the shape that is cut away afterwards,
+/// or by the layers of a stacked shape that end up above [`MAX_LAYERS`].
+///
+/// # Example
+///
+/// ```
+/// use shapez_core::{RecipeError, Shape, find_recipe};
+///
+/// let target: Shape = "CrCr----:--RgRg--".parse().unwrap();
+/// let recipe = find_recipe(&target).unwrap();
+/// assert_eq!(recipe.evaluate(), target);
+///
+/// let target: Shape = "Cu------:----Cu--".parse().unwrap();
+/// assert_eq!(find_recipe(&target), Err(RecipeError::Floating { layers: vec![2] }));
+/// ```
+pub fn find_recipe(target: &Shape) -> Result<Recipe, RecipeError> {
+    // Ensure the shape is one the operations can produce at all
+    if target.is_empty() {
+        return Err(RecipeError::EmptyShape);
+    }
+    if target.layers.len() > MAX_LAYERS {
+        return Err(RecipeError::TooManyLayers {
+            count: target.layers.len(),
+            max: MAX_LAYERS,
+        });
+    }
+    if let Some(index) = target
+        .layers
+        .iter()
+        .position(|layer| layer.iter().all(Option::is_none))
+    {
+        return Err(RecipeError::EmptyLayer { layer: index + 1 });
+    }
+
+    let pattern = to_pattern(&target.layers);
+    let mut solver = Solver::default();
+    if !solver.solve(pattern) {
+        let layers = (1..target.layers.len())
+            .filter(|&index| layer_bits(pattern, index) & layer_bits(pattern, index - 1) == 0)
+            .map(|index| index + 1)
+            .collect();
+        return Err(RecipeError::Floating { layers });
+    }
+
+    let recipe = solver.build(pattern, &target.layers);
+    debug_assert_eq!(recipe.evaluate(), *target);
+    Ok(recipe)
+}
+
+/// Same as [`validate_short_key`], but also rejecting shapes no recipe can build,
+/// pointing at every quad of the layers that do not rest on the layer below them.
+///
+/// This is the validator `shapez_shape!(strict, ...)` runs at compile time.
+///
+/// # Example
+///
+/// ```
+/// use shapez_core::{validate_buildable_short_key, ShapeKeyError};
+///
+/// assert!(validate_buildable_short_key("CuCu----:--Cu----").is_ok());
+/// assert_eq!(
+///     validate_buildable_short_key("Cu------:----Cu--"),
+///     Err(vec![ShapeKeyError::UnsupportedQuad { layer: 2, quad: 3 }])
+/// );
+/// ```
+pub fn validate_buildable_short_key(key: &str) -> Result<Shape, Vec<ShapeKeyError>> {
+    let shape = validate_short_key(key)?;
+    match find_recipe(&shape) {
+        Ok(_) => Ok(shape),
+        Err(RecipeError::Floating { layers }) => Err(layers
+            .into_iter()
+            .flat_map(|layer| {
+                let quads = &shape.layers[layer - 1];
+                (0..QUADS_AMOUNT)
+                    .filter(|&quad| quads[quad].is_some())
+                    .map(move |quad| ShapeKeyError::UnsupportedQuad {
+                        layer,
+                        quad: quad + 1,
+                    })
+            })
+            .collect()),
+        // The validator already rejects empty layers and too many layers
+        Err(err) => unreachable!("valid short key rejected by the recipe search: {err}"),
+    }
+}
+
+/// The filled quads of a shape, [`QUADS_AMOUNT`] bits for each of up to [`MAX_LAYERS`] layers,
+/// starting from the bottom layer.
+type Pattern = u16;
+
+const LAYER_MASK: u8 = (1 << QUADS_AMOUNT) - 1;
+
+/// The quads of the east half, the one the cutter keeps to the east.
+const EAST: u8 = 0b0011;
+
+/// The quad of the west half holding up the layers of an east half.
+const SCAFFOLD_QUAD: usize = 2;
+
+/// What is left for the scaffolding cut away in the end.
+const SCAFFOLD: Quad = Quad(Subshape::Circle, Color::Uncolored);
+
+fn layer_bits(pattern: Pattern, layer: usize) -> u8 {
+    (pattern >> (layer * QUADS_AMOUNT)) as u8 & LAYER_MASK
+}
+
+fn pattern_layers(pattern: Pattern) -> [u8; MAX_LAYERS] {
+    std::array::from_fn(|layer| layer_bits(pattern, layer))
+}
+
+/// Packs the layers into a pattern, dropping empty layers like the cutter does.
+fn from_layers(layers: &[u8]) -> Pattern {
+    layers
+        .iter()
+        .filter(|&&bits| bits != 0)
+        .take(MAX_LAYERS)
+        .enumerate()
+        .fold(0, |pattern, (layer, &bits)| {
+            pattern | Pattern::from(bits) << (layer * QUADS_AMOUNT)
+        })
+}
+
+fn to_pattern(layers: &[Layer]) -> Pattern {
+    let bits = layers
+        .iter()
+        .map(|layer| {
+            (0..QUADS_AMOUNT)
+                .filter(|&quad| layer[quad].is_some())
+                .fold(0, |bits, quad| bits | 1 << quad)
+        })
+        .collect::<Vec<u8>>();
+    from_layers(&bits)
+}
+
+fn height(pattern: Pattern) -> usize {
+    pattern_layers(pattern)
+        .iter()
+        .take_while(|&&bits| bits != 0)
+        .count()
+}
+
+/// Rotates every layer clockwise by `steps` quads, like [`Shape::rotate_cw`].
+fn rotate_pattern(pattern: Pattern, steps: usize) -> Pattern {
+    let steps = steps % QUADS_AMOUNT;
+    let layers = pattern_layers(pattern).map(|bits| {
+        ((bits << steps) | (bits >> ((QUADS_AMOUNT - steps) % QUADS_AMOUNT))) & LAYER_MASK
+    });
+    from_layers(&layers)
+}
+
+/// Returns the layer the stacker puts the lowest layer of `top` at, see [`Shape::stack`].
+fn landing_layer(bottom: Pattern, top: Pattern) -> usize {
+    let bottom = pattern_layers(bottom);
+    let mut landing = 0;
+    for (index, bits) in pattern_layers(top).into_iter().enumerate() {
+        for quad in (0..QUADS_AMOUNT).filter(|&quad| bits & 1 << quad != 0) {
+            if let Some(resting_on) = bottom.iter().rposition(|&bits| bits & 1 << quad != 0) {
+                landing = landing.max((resting_on + 1).saturating_sub(index));
+            }
+        }
+    }
+
+    landing
+}
+
+/// Returns how many clockwise steps put the whole pattern into the east half.
+fn east_half_rotation(pattern: Pattern) -> Option<usize> {
+    (0..QUADS_AMOUNT).find(|&steps| {
+        pattern_layers(rotate_pattern(pattern, steps))
+            .iter()
+            .all(|&bits| bits & !EAST == 0)
+    })
+}
+
+/// Returns the smallest rotation of a pattern and the clockwise steps rotating it back.
+fn canonical(pattern: Pattern) -> (Pattern, usize) {
+    (0..QUADS_AMOUNT)
+        .map(|steps| {
+            (
+                rotate_pattern(pattern, steps),
+                (QUADS_AMOUNT - steps) % QUADS_AMOUNT,
+            )
+        })
+        .min()
+        .expect("at least one rotation")
+}
+
+/// The last operation building a pattern.
+#[derive(Clone, Copy)]
+enum Step {
+    /// Stacked quad by quad, as the pattern has a single layer
+    Layer,
+    /// Cut out of a larger shape, as the pattern fits into a single half
+    Half,
+    /// Stacked from two smaller patterns
+    Stack { bottom: Pattern, top: Pattern },
+}
+
+/// Memoizes the patterns that can be built, only looking at patterns in their smallest rotation.
+struct Solver {
+    /// `None` for unknown patterns, `Some(None)` for patterns that can't be built (yet)
+    steps: Vec<Option<Option<Step>>>,
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Self {
+            steps: vec![None; usize::from(Pattern::MAX) + 1],
+        }
+    }
+}
+
+impl Solver {
+    fn solve(&mut self, pattern: Pattern) -> bool {
+        let (pattern, _) = canonical(pattern);
+        if let Some(step) = self.steps[usize::from(pattern)] {
+            return step.is_some();
+        }
+
+        let step = if height(pattern) == 1 {
+            Some(Step::Layer)
+        } else if east_half_rotation(pattern).is_some() {
+            Some(Step::Half)
+        } else {
+            // Patterns depending on themselves can't be built that way
+            self.steps[usize::from(pattern)] = Some(None);
+            self.find_stack(pattern)
+        };
+        self.steps[usize::from(pattern)] = Some(step);
+        step.is_some()
+    }
+
+    /// Looks for a bottom and a top pattern stacking into `pattern`.
+    fn find_stack(&mut self, pattern: Pattern) -> Option<Step> {
+        // Stacking the top layer onto the layers below is the simplest recipe, so it goes first
+        let below = pattern & ((1 << ((height(pattern) - 1) * QUADS_AMOUNT)) - 1);
+        let parts = std::iter::successors(Some(pattern), |&part| {
+            part.checked_sub(1).map(|part| part & pattern)
+        })
+        .skip(1);
+
+        std::iter::once(below)
+            .chain(parts)
+            .filter(|&bottom| bottom != 0)
+            .find_map(|bottom| self.find_top(pattern, bottom))
+    }
+
+    /// Looks for a top pattern stacking onto `bottom` into `pattern`.
+    fn find_top(&mut self, pattern: Pattern, bottom: Pattern) -> Option<Step> {
+        if from_layers(&pattern_layers(bottom)) != bottom {
+            return None;
+        }
+
+        // The rest has to fall down as a whole, landing on its lowest layer
+        let bottom_layers = pattern_layers(bottom);
+        let rest: [u8; MAX_LAYERS] =
+            std::array::from_fn(|layer| layer_bits(pattern, layer) & !bottom_layers[layer]);
+        let lowest = rest.iter().position(|&bits| bits != 0)?;
+        let top_height = rest[lowest..].iter().take_while(|&&bits| bits != 0).count();
+        if rest[lowest + top_height..].iter().any(|&bits| bits != 0) {
+            return None;
+        }
+        let top = from_layers(&rest);
+        let landing = landing_layer(bottom, top);
+        if landing > lowest {
+            return None;
+        }
+        if landing == lowest {
+            return (self.solve(bottom) && self.solve(top)).then_some(Step::Stack { bottom, top });
+        }
+
+        // Layers above the limit can still hold the top up, as long as it reaches the limit
+        if lowest + top_height < MAX_LAYERS || !self.solve(bottom) {
+            return None;
+        }
+        for extra in 1..=MAX_LAYERS - top_height {
+            let combinations = usize::from(LAYER_MASK).pow(extra as u32);
+            for combination in 0..combinations {
+                let mut top_layers = [0; MAX_LAYERS];
+                top_layers[..top_height].copy_from_slice(&rest[lowest..]);
+                let mut remaining = combination;
+                for bits in &mut top_layers[top_height..top_height + extra] {
+                    *bits = (remaining % usize::from(LAYER_MASK)) as u8 + 1;
+                    remaining /= usize::from(LAYER_MASK);
+                }
+
+                let top = from_layers(&top_layers);
+                if landing_layer(bottom, top) == lowest && self.solve(top) {
+                    return Some(Step::Stack { bottom, top });
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Builds the recipe of a solved pattern, filling its quads with `quads`.
+    fn build(&self, pattern: Pattern, quads: &[Layer]) -> Recipe {
+        let (canonical, steps) = canonical(pattern);
+        let quads = rotate_quads(quads, QUADS_AMOUNT - steps);
+        let step = self.steps[usize::from(canonical)]
+            .flatten()
+            .expect("a solved pattern");
+
+        let recipe = match step {
+            Step::Layer => build_layer(&quads[0]),
+            Step::Half => {
+                let to_east = east_half_rotation(canonical).expect("a pattern in a single half");
+                let quads = rotate_quads(&quads, to_east);
+                build_half(&quads).rotated(QUADS_AMOUNT - to_east)
+            }
+            Step::Stack { bottom, top } => {
+                let bottom_layers = pattern_layers(bottom);
+                let bottom_quads = quads
+                    .iter()
+                    .zip(bottom_layers)
+                    .take(height(bottom))
+                    .map(|(layer, bits)| keep_quads(layer, bits))
+                    .collect::<Vec<_>>();
+
+                // Layers of the top above the limit are cut off, so they hold anything
+                let landing = landing_layer(bottom, top);
+                let top_quads = pattern_layers(top)
+                    .into_iter()
+                    .take(height(top))
+                    .enumerate()
+                    .map(|(index, bits)| match quads.get(landing + index) {
+                        Some(layer) => keep_quads(layer, bits),
+                        None => fill_quads(bits, SCAFFOLD),
+                    })
+                    .collect::<Vec<_>>();
+
+                let bottom = self.build(bottom, &bottom_quads);
+                let top = self.build(top, &top_quads);
+                bottom.stack(top)
+            }
+        };
+
+        recipe.rotated(steps)
+    }
+}
+
+fn keep_quads(layer: &Layer, bits: u8) -> Layer {
+    std::array::from_fn(|quad| layer[quad].filter(|_| bits & 1 << quad != 0))
+}
+
+fn fill_quads(bits: u8, quad: Quad) -> Layer {
+    std::array::from_fn(|index| Some(quad).filter(|_| bits & 1 << index != 0))
+}
+
+fn rotate_quads(quads: &[Layer], steps: usize) -> Vec<Layer> {
+    let mut layers = quads.to_vec();
+    for layer in &mut layers {
+        layer.rotate_right(steps % QUADS_AMOUNT);
+    }
+
+    layers
+}
+
+/// Builds a shape fitting into the east half.
+///
+/// Shapes of several layers are built with a column of scaffolding in the west half holding up every layer,
+/// which is cut away in the end.
+fn build_half(quads: &[Layer]) -> Recipe {
+    if let [layer] = quads {
+        return build_layer(layer);
+    }
+
+    let mut layers = quads.iter().map(|layer| {
+        let mut layer = *layer;
+        layer[SCAFFOLD_QUAD] = Some(SCAFFOLD);
+        build_layer(&layer)
+    });
+    let first = layers.next().expect("at least one layer");
+    let scaffolded = layers.fold(first, Recipe::stack);
+    Recipe::CutEast(Box::new(scaffolded))
+}
+
+/// Builds a single layer, quad by quad unless it is a basic shape as a whole.
+fn build_layer(layer: &Layer) -> Recipe {
+    if let Some(quad) = layer[0].filter(|quad| layer.iter().all(|other| *other == Some(*quad))) {
+        return build_basic(quad);
+    }
+
+    let mut quads = layer
+        .iter()
+        .enumerate()
+        .filter_map(|(index, quad)| Some(build_quad(index, (*quad)?)));
+    let first = quads.next().expect("a layer with at least one quad");
+    quads.fold(first, Recipe::stack)
+}
+
+fn build_basic(Quad(sub_shape, color): Quad) -> Recipe {
+    let basic = Recipe::Extract(sub_shape);
+    match color {
+        Color::Uncolored => basic,
+        color => Recipe::Paint(Box::new(basic), color),
+    }
+}
+
+/// Builds a single quad at `index`, cutting it out of a basic shape.
+fn build_quad(index: usize, quad: Quad) -> Recipe {
+    // The east half, rotated onto quads 2 and 3, only keeps quad 2 when cut again
+    let east = Recipe::CutEast(Box::new(build_basic(quad)));
+    let second = Recipe::CutEast(Box::new(east.rotated(1)));
+    second.rotated(index + QUADS_AMOUNT - 1)
+}
@@ -0,0 +1,282 @@
+//! Rendering shapes the way [viewer.shapez.io](https://viewer.shapez.io) draws them,
+//! either into SVG images or into PNG images through a software rasterizer.
+//!
+//! Only available with the `render` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use shapez_core::Shape;
+//! use shapez_core::render::{self, RenderOptions};
+//!
+//! let shape: Shape = "RuCw--Cw:----Ru--".parse().unwrap();
+//! let svg = render::svg(&shape, RenderOptions::default());
+//! assert!(svg.starts_with("<svg"));
+//!
+//! let png = render::png(&shape, RenderOptions { size: 64, ..RenderOptions::default() });
+//! assert!(png.starts_with(b"\x89PNG"));
+//! ```
+
+mod png;
+mod raster;
+
+use crate::{Color, QUADS_AMOUNT, Quad, Shape, Subshape};
+use std::fmt::Write;
+
+/// Half the width of the image in the units of the viewer, where a quad of the bottom layer is 9 units wide.
+const VIEW_RADIUS: f64 = 14.0;
+
+/// The width of a quad before scaling it down for its layer.
+const QUAD_SIZE: f64 = 10.0;
+
+/// The radius of the faint circle behind the shape.
+const SHADOW_RADIUS: f64 = QUAD_SIZE * 1.15;
+
+const SHADOW_COLOR: [u8; 3] = [40, 50, 65];
+const SHADOW_OPACITY: f64 = 0.1;
+
+const OUTLINE_COLOR: [u8; 3] = [0x55, 0x57, 0x5a];
+const OUTLINE_WIDTH: f64 = 0.75;
+
+/// How a shape is rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderOptions {
+    /// The width and height of the image, in pixels.
+    pub size: u32,
+    /// Whether to draw the faint circle the viewer draws behind the shape.
+    pub shadow: bool,
+    /// The RGB color filling the image behind the shape, transparent if `None`.
+    pub background: Option<[u8; 3]>,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            size: 512,
+            shadow: true,
+            background: None,
+        }
+    }
+}
+
+/// Renders the shape into a standalone SVG image.
+///
+/// Each quad is drawn with the sub-shape, colors and outline of the viewer,
+/// and every layer is drawn smaller than the one below it, the same way the viewer scales them.
+/// The sub-shapes are drawn the way the viewer draws their letters,
+/// so `R` fills the whole quad and `S` is drawn as the pointed star of the game.
+///
+/// # Example
+///
+/// ```
+/// use shapez_core::Shape;
+/// use shapez_core::render::{self, RenderOptions};
+///
+/// let shape: Shape = "Cr------".parse().unwrap();
+/// let options = RenderOptions { size: 64, shadow: false, background: None };
+/// assert_eq!(
+///     render::svg(&shape, options),
+///     "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"64\" height=\"64\" viewBox=\"-14 -14 28 28\">\n\
+///      <g stroke=\"#55575a\" stroke-width=\"0.75\">\n\
+///      <path fill=\"#ff666a\" d=\"M0 0L0 -9A9 9 0 0 1 9 0Z\"/>\n\
+///      </g>\n\
+///      </svg>\n"
+/// );
+/// ```
+pub fn svg(shape: &Shape, options: RenderOptions) -> String {
+    let mut svg = String::new();
+    let size = options.size;
+    let view = num(-VIEW_RADIUS);
+    let width = num(VIEW_RADIUS * 2.0);
+    // Writing into a `String` never fails
+    let _ = writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{size}\" height=\"{size}\" viewBox=\"{view} {view} {width} {width}\">"
+    );
+    if let Some(background) = options.background {
+        let _ = writeln!(
+            svg,
+            "<rect x=\"{view}\" y=\"{view}\" width=\"{width}\" height=\"{width}\" fill=\"{}\"/>",
+            hex(background)
+        );
+    }
+    if options.shadow {
+        let _ = writeln!(
+            svg,
+            "<circle r=\"{}\" fill=\"{}\" fill-opacity=\"{}\"/>",
+            num(SHADOW_RADIUS),
+            hex(SHADOW_COLOR),
+            num(SHADOW_OPACITY)
+        );
+    }
+
+    let _ = writeln!(
+        svg,
+        "<g stroke=\"{}\" stroke-width=\"{}\">",
+        hex(OUTLINE_COLOR),
+        num(OUTLINE_WIDTH)
+    );
+    for (index, layer) in shape.layers.iter().enumerate() {
+        let size = quad_size(index);
+        for (quad, &part) in layer.iter().enumerate() {
+            let Some(Quad(sub_shape, color)) = part else {
+                continue;
+            };
+
+            let transform = match quad {
+                0 => String::new(),
+                _ => format!(" transform=\"rotate({})\"", quad * 360 / QUADS_AMOUNT),
+            };
+            let _ = writeln!(
+                svg,
+                "<path{transform} fill=\"{}\" d=\"{}\"/>",
+                hex(palette(color)),
+                path(&outline(sub_shape, size))
+            );
+        }
+    }
+    svg.push_str("</g>\n</svg>\n");
+
+    svg
+}
+
+/// Renders the shape into a PNG image, `options.size` pixels wide and high.
+///
+/// The image is rasterized in software from the same outlines as [`svg`], with anti-aliased edges.
+/// Without a background, everything around the shape is transparent.
+///
+/// # Panics
+///
+/// Panics if `options.size` is 0, PNG images are at least a pixel wide.
+///
+/// # Example
+///
+/// ```
+/// use shapez_core::Shape;
+/// use shapez_core::render::{self, RenderOptions};
+///
+/// let shape: Shape = "CuCuCuCu:RrRrRrRr".parse().unwrap();
+/// let options = RenderOptions { size: 32, shadow: false, background: Some([0x28, 0x32, 0x41]) };
+/// let png = render::png(&shape, options);
+/// assert_eq!(png[1..4], *b"PNG");
+/// ```
+pub fn png(shape: &Shape, options: RenderOptions) -> Vec<u8> {
+    png::encode(options.size, options.size, &rgba(shape, options))
+}
+
+/// Rasterizes the shape into RGBA pixels like [`png`], without encoding them.
+///
+/// The pixels are returned row by row from the top left corner, 4 bytes per pixel.
+/// Colors are not premultiplied by their alpha.
+///
+/// # Panics
+///
+/// Panics if `options.size` is 0, like [`png`].
+///
+/// # Example
+///
+/// ```
+/// use shapez_core::Shape;
+/// use shapez_core::render::{self, RenderOptions};
+///
+/// let shape: Shape = "RrRrRrRr".parse().unwrap();
+/// let options = RenderOptions { size: 28, shadow: false, background: None };
+/// let pixels = render::rgba(&shape, options);
+/// assert_eq!(pixels.len(), 28 * 28 * 4);
+///
+/// // The corners are transparent, the middle of the top right quad is red
+/// assert_eq!(pixels[..4], [0, 0, 0, 0]);
+/// let quad = (10 * 28 + 18) * 4;
+/// assert_eq!(pixels[quad..quad + 4], [0xff, 0x66, 0x6a, 0xff]);
+/// ```
+pub fn rgba(shape: &Shape, options: RenderOptions) -> Vec<u8> {
+    assert!(options.size > 0, "can not render an image of size 0");
+    raster::rasterize(shape, options)
+}
+
+/// Returns the width of the quads of the layer at `index`, counting from the bottom layer.
+fn quad_size(index: usize) -> f64 {
+    QUAD_SIZE * (0.9 - index as f64 * 0.22).max(0.1)
+}
+
+/// Returns the color the viewer fills quads of `color` with.
+fn palette(color: Color) -> [u8; 3] {
+    match color {
+        Color::Red => [0xff, 0x66, 0x6a],
+        Color::Green => [0x78, 0xff, 0x66],
+        Color::Blue => [0x66, 0xa7, 0xff],
+        Color::Yellow => [0xfc, 0xf5, 0x2a],
+        Color::Purple => [0xdd, 0x66, 0xff],
+        Color::Cyan => [0x87, 0xff, 0xf5],
+        Color::White => [0xff, 0xff, 0xff],
+        Color::Uncolored => [0xaa, 0xaa, 0xaa],
+    }
+}
+
+/// A point in the units of the viewer, with the center of the shape at the origin and `y` pointing down.
+type Point = (f64, f64);
+
+/// A piece of the outline of a quad, starting where the previous one ended.
+enum Edge {
+    Line(Point),
+    /// A clockwise arc around the center of the shape.
+    Arc(f64, Point),
+}
+
+/// Returns the outline of a quad `size` units wide, in the top right corner of the shape.
+///
+/// Every outline starts at the center of the shape and is closed back to it.
+/// The other quads are the same outline rotated clockwise around the center of the shape.
+fn outline(sub_shape: Subshape, size: f64) -> Vec<Edge> {
+    // The inner corner of stars and windmills
+    let inwards = size * 0.6;
+    match sub_shape {
+        Subshape::Circle => vec![Edge::Line((0.0, -size)), Edge::Arc(size, (size, 0.0))],
+        Subshape::Rectangle => vec![
+            Edge::Line((0.0, -size)),
+            Edge::Line((size, -size)),
+            Edge::Line((size, 0.0)),
+        ],
+        Subshape::Square => vec![
+            Edge::Line((0.0, -inwards)),
+            Edge::Line((size, -size)),
+            Edge::Line((inwards, 0.0)),
+        ],
+        Subshape::Windmill => vec![
+            Edge::Line((0.0, -inwards)),
+            Edge::Line((size, -size)),
+            Edge::Line((size, 0.0)),
+        ],
+    }
+}
+
+/// Writes the outline as the data of an SVG path.
+fn path(outline: &[Edge]) -> String {
+    let mut path = String::from("M0 0");
+    for edge in outline {
+        let _ = match *edge {
+            Edge::Line((x, y)) => write!(path, "L{} {}", num(x), num(y)),
+            Edge::Arc(radius, (x, y)) => {
+                let radius = num(radius);
+                write!(path, "A{radius} {radius} 0 0 1 {} {}", num(x), num(y))
+            }
+        };
+    }
+    path.push('Z');
+
+    path
+}
+
+fn hex([r, g, b]: [u8; 3]) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// Formats a number with at most 3 decimals, so the output does not depend on rounding errors.
+fn num(value: f64) -> String {
+    let rounded = format!("{value:.3}");
+    let trimmed = rounded.trim_end_matches('0').trim_end_matches('.');
+    match trimmed {
+        "-0" => "0".to_string(),
+        _ => trimmed.to_string(),
+    }
+}
@@ -0,0 +1,258 @@
+//! A minimal PNG encoder for 8-bit RGBA images, compressing with fixed Huffman codes.
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+/// Encodes `pixels`, `width` RGBA pixels per row, into a PNG file.
+pub(super) fn encode(width: u32, height: u32, pixels: &[u8]) -> Vec<u8> {
+    let mut header = vec![];
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    // 8 bits per channel, RGBA, default compression, filtering and no interlacing
+    header.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+    // Every row starts with its filter type, none
+    let row = width as usize * 4;
+    let mut data = Vec::with_capacity((row + 1) * height as usize);
+    for line in pixels.chunks(row.max(1)).take(height as usize) {
+        data.push(0);
+        data.extend_from_slice(line);
+    }
+
+    let mut png = SIGNATURE.to_vec();
+    write_chunk(&mut png, b"IHDR", &header);
+    write_chunk(&mut png, b"IDAT", &zlib(&data));
+    write_chunk(&mut png, b"IEND", &[]);
+
+    png
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+const CRC_TABLE: [u32; 256] = crc_table();
+
+const fn crc_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut index = 0;
+    while index < 256 {
+        let mut crc = index as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                0xedb8_8320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[index] = crc;
+        index += 1;
+    }
+
+    table
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0, |crc, &byte| {
+        CRC_TABLE[((crc ^ u32::from(byte)) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (a, b) = bytes.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + u32::from(byte)) % 65521;
+        (a, (b + a) % 65521)
+    });
+    b << 16 | a
+}
+
+/// Wraps the deflated bytes into a zlib stream.
+fn zlib(bytes: &[u8]) -> Vec<u8> {
+    // Deflate with a 32K window, no preset dictionary and the check bits of the flags
+    let mut stream = vec![0x78, 0x01];
+    stream.extend(deflate(bytes));
+    stream.extend_from_slice(&adler32(bytes).to_be_bytes());
+
+    stream
+}
+
+const WINDOW: usize = 1 << 15;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+/// How many earlier positions with the same hash are tried for each match.
+const MAX_CHAIN: usize = 32;
+
+const LENGTH_BASES: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASES: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+/// Compresses the bytes into a single deflate block with the fixed Huffman codes.
+fn deflate(bytes: &[u8]) -> Vec<u8> {
+    let mut out = BitWriter::default();
+    // The final block, compressed with fixed codes
+    out.write(1, 1);
+    out.write(0b01, 2);
+
+    let mut matcher = Matcher::new(bytes);
+    let mut index = 0;
+    while index < bytes.len() {
+        let (length, distance) = matcher.longest_match(index);
+        let length = if length >= MIN_MATCH {
+            write_match(&mut out, length, distance);
+            length
+        } else {
+            write_literal(&mut out, u16::from(bytes[index]));
+            1
+        };
+        for position in index..index + length {
+            matcher.insert(position);
+        }
+        index += length;
+    }
+    write_literal(&mut out, 256);
+
+    out.finish()
+}
+
+/// Remembers the earlier positions of every 3 bytes, chained by their hash.
+struct Matcher<'a> {
+    bytes: &'a [u8],
+    heads: Vec<usize>,
+    previous: Vec<usize>,
+}
+
+impl<'a> Matcher<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            heads: vec![usize::MAX; 1 << 15],
+            previous: vec![usize::MAX; bytes.len()],
+        }
+    }
+
+    fn hash(&self, index: usize) -> usize {
+        let value = u32::from(self.bytes[index]) << 16
+            | u32::from(self.bytes[index + 1]) << 8
+            | u32::from(self.bytes[index + 2]);
+        (value.wrapping_mul(0x9e37_79b1) >> 17) as usize
+    }
+
+    fn insert(&mut self, index: usize) {
+        if index + MIN_MATCH <= self.bytes.len() {
+            let hash = self.hash(index);
+            self.previous[index] = self.heads[hash];
+            self.heads[hash] = index;
+        }
+    }
+
+    /// Finds the longest earlier repetition of the bytes at `index`, as its length and distance.
+    fn longest_match(&self, index: usize) -> (usize, usize) {
+        if index + MIN_MATCH > self.bytes.len() {
+            return (0, 0);
+        }
+
+        let max = MAX_MATCH.min(self.bytes.len() - index);
+        let mut best = (0, 0);
+        let mut candidate = self.heads[self.hash(index)];
+        for _ in 0..MAX_CHAIN {
+            if candidate == usize::MAX || index - candidate > WINDOW {
+                break;
+            }
+            let length = self.bytes[candidate..]
+                .iter()
+                .zip(&self.bytes[index..index + max])
+                .take_while(|(a, b)| a == b)
+                .count();
+            if length > best.0 {
+                best = (length, index - candidate);
+                if length == max {
+                    break;
+                }
+            }
+            candidate = self.previous[candidate];
+        }
+
+        best
+    }
+}
+
+fn write_literal(out: &mut BitWriter, value: u16) {
+    let (code, bits) = match value {
+        0..=143 => (0x30 + value, 8),
+        144..=255 => (0x190 + value - 144, 9),
+        256..=279 => (value - 256, 7),
+        _ => (0xc0 + value - 280, 8),
+    };
+    out.write_code(code, bits);
+}
+
+fn write_match(out: &mut BitWriter, length: usize, distance: usize) {
+    let index = LENGTH_BASES.partition_point(|&base| usize::from(base) <= length) - 1;
+    write_literal(out, 257 + index as u16);
+    out.write(
+        (length - usize::from(LENGTH_BASES[index])) as u32,
+        LENGTH_EXTRA[index],
+    );
+
+    let index = DISTANCE_BASES.partition_point(|&base| usize::from(base) <= distance) - 1;
+    out.write_code(index as u16, 5);
+    out.write(
+        (distance - usize::from(DISTANCE_BASES[index])) as u32,
+        DISTANCE_EXTRA[index],
+    );
+}
+
+/// Writes bits starting from the least significant bit of each byte, the way deflate packs them.
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    len: u8,
+}
+
+impl BitWriter {
+    /// Writes the lowest `bits` bits of `value`, least significant bit first.
+    fn write(&mut self, value: u32, bits: u8) {
+        for bit in 0..bits {
+            self.buffer |= (value >> bit & 1) << self.len;
+            self.len += 1;
+            if self.len == 8 {
+                self.bytes.push(self.buffer as u8);
+                self.buffer = 0;
+                self.len = 0;
+            }
+        }
+    }
+
+    /// Writes a Huffman code, which is packed most significant bit first.
+    fn write_code(&mut self, code: u16, bits: u8) {
+        let reversed = u32::from(code.reverse_bits() >> (16 - bits));
+        self.write(reversed, bits);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.len > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+
+        self.bytes
+    }
+}
@@ -0,0 +1,161 @@
+//! A software rasterizer drawing the same outlines as the SVG renderer.
+//!
+//! Coverage is computed from the distance of each pixel center to the outlines,
+//! which anti-aliases every edge over the width of a single pixel.
+
+use super::{
+    Edge, OUTLINE_COLOR, OUTLINE_WIDTH, Point, RenderOptions, SHADOW_COLOR, SHADOW_OPACITY,
+    SHADOW_RADIUS, VIEW_RADIUS, outline, palette, quad_size,
+};
+use crate::{QUADS_AMOUNT, Quad, Shape};
+
+/// A color with its alpha premultiplied into the other channels, from 0 to 1.
+type Premultiplied = [f64; 4];
+
+/// Rasterizes the shape into rows of straight, non-premultiplied RGBA pixels.
+pub(super) fn rasterize(shape: &Shape, options: RenderOptions) -> Vec<u8> {
+    let size = options.size as usize;
+    // The width of a pixel in the units of the viewer
+    let pixel = VIEW_RADIUS * 2.0 / options.size as f64;
+
+    let outlines = shape
+        .layers
+        .iter()
+        .enumerate()
+        .flat_map(|(index, layer)| {
+            layer.iter().enumerate().filter_map(move |(quad, &part)| {
+                let Quad(sub_shape, color) = part?;
+                let size = quad_size(index);
+                Some((quad, size, outline(sub_shape, size), opaque(palette(color))))
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut pixels = Vec::with_capacity(size * size * 4);
+    for row in 0..size {
+        for column in 0..size {
+            let point = (
+                (column as f64 + 0.5) * pixel - VIEW_RADIUS,
+                (row as f64 + 0.5) * pixel - VIEW_RADIUS,
+            );
+
+            let mut color = options.background.map_or([0.0; 4], opaque);
+            if options.shadow {
+                let distance = (point.0.hypot(point.1) - SHADOW_RADIUS) / pixel;
+                let shadow = opaque(SHADOW_COLOR).map(|channel| channel * SHADOW_OPACITY);
+                color = over(color, shadow, coverage(distance));
+            }
+
+            for (quad, size, outline, fill) in &outlines {
+                let point = rotate_ccw(point, *quad);
+                // Skip the quads this pixel is nowhere near
+                let margin = OUTLINE_WIDTH + pixel;
+                if point.0 < -margin
+                    || point.1 > margin
+                    || point.0 > size + margin
+                    || point.1 < -size - margin
+                {
+                    continue;
+                }
+
+                let distance = signed_distance(outline, point);
+                color = over(color, *fill, coverage(distance / pixel));
+                let stroke = distance.abs() - OUTLINE_WIDTH / 2.0;
+                color = over(color, opaque(OUTLINE_COLOR), coverage(stroke / pixel));
+            }
+
+            pixels.extend(straight(color));
+        }
+    }
+
+    pixels
+}
+
+fn opaque(rgb: [u8; 3]) -> Premultiplied {
+    let [r, g, b] = rgb.map(|channel| f64::from(channel) / 255.0);
+    [r, g, b, 1.0]
+}
+
+/// Returns how much of a pixel is covered by a shape `distance` pixels away from its center.
+fn coverage(distance: f64) -> f64 {
+    (0.5 - distance).clamp(0.0, 1.0)
+}
+
+/// Draws `top`, covering `coverage` of the pixel, over `bottom`.
+fn over(bottom: Premultiplied, top: Premultiplied, coverage: f64) -> Premultiplied {
+    let alpha = top[3] * coverage;
+    std::array::from_fn(|channel| top[channel] * coverage + bottom[channel] * (1.0 - alpha))
+}
+
+fn straight(color: Premultiplied) -> [u8; 4] {
+    let alpha = color[3];
+    let channel = |value: f64| match alpha {
+        0.0 => 0,
+        _ => (value / alpha * 255.0).round().clamp(0.0, 255.0) as u8,
+    };
+    [
+        channel(color[0]),
+        channel(color[1]),
+        channel(color[2]),
+        (alpha * 255.0).round().clamp(0.0, 255.0) as u8,
+    ]
+}
+
+/// Rotates the point counter-clockwise by `quads` quads around the center of the shape,
+/// moving a point of that quad into the top right quad the outlines are drawn in.
+fn rotate_ccw(point: Point, quads: usize) -> Point {
+    (0..quads % QUADS_AMOUNT).fold(point, |(x, y), _| (y, -x))
+}
+
+/// Returns the distance of the point to the outline, negative inside of it.
+///
+/// Every outline is convex, so a point is inside when it is on the inner side of every edge.
+fn signed_distance(outline: &[Edge], point: Point) -> f64 {
+    let mut distance = f64::INFINITY;
+    let mut inside = true;
+    let mut from = (0.0, 0.0);
+    // Close the outline back to the center of the shape
+    for edge in outline.iter().chain([&Edge::Line((0.0, 0.0))]) {
+        match *edge {
+            Edge::Line(to) => {
+                distance = distance.min(segment_distance(point, from, to));
+                // The outlines run clockwise, so the inside is on the right of each edge
+                let cross =
+                    (to.0 - from.0) * (point.1 - from.1) - (to.1 - from.1) * (point.0 - from.0);
+                inside &= cross >= 0.0;
+                from = to;
+            }
+            Edge::Arc(radius, to) => {
+                distance = distance.min(arc_distance(point, radius, from, to));
+                inside &= point.0.hypot(point.1) <= radius;
+                from = to;
+            }
+        }
+    }
+
+    if inside { -distance } else { distance }
+}
+
+fn segment_distance(point: Point, from: Point, to: Point) -> f64 {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let length = dx * dx + dy * dy;
+    let t = if length == 0.0 {
+        0.0
+    } else {
+        (((point.0 - from.0) * dx + (point.1 - from.1) * dy) / length).clamp(0.0, 1.0)
+    };
+
+    (point.0 - from.0 - t * dx).hypot(point.1 - from.1 - t * dy)
+}
+
+/// Returns the distance to a clockwise arc around the center of the shape.
+fn arc_distance(point: Point, radius: f64, from: Point, to: Point) -> f64 {
+    let angle = point.1.atan2(point.0);
+    let (start, end) = (from.1.atan2(from.0), to.1.atan2(to.0));
+    if (start..=end).contains(&angle) {
+        (point.0.hypot(point.1) - radius).abs()
+    } else {
+        let distance = |corner: Point| (point.0 - corner.0).hypot(point.1 - corner.1);
+        distance(from).min(distance(to))
+    }
+}
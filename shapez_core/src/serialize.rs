@@ -0,0 +1,148 @@
+//! Serde support for shapes, only available with the `serde` feature.
+
+use crate::parse::check_short_layers;
+use crate::{QUADS_AMOUNT, Quad, Shape, ShapeKeyError, StaticShape, validate_short_key};
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+use std::fmt;
+
+/// Serializes the shape as its short key, like `"RuCw--Cw:----Ru--"`.
+impl Serialize for Shape {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Serializes the shape as its short key, like `"RuCw--Cw:----Ru--"`.
+impl Serialize for StaticShape {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Deserializes a short key through [`validate_short_key`],
+/// reporting every problem of the key along with the column it starts at.
+impl<'de> Deserialize<'de> for Shape {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(ShortKeyVisitor)
+    }
+}
+
+struct ShortKeyVisitor;
+
+impl Visitor<'_> for ShortKeyVisitor {
+    type Value = Shape;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a short shape key like \"RuCw--Cw:----Ru--\"")
+    }
+
+    fn visit_str<E: de::Error>(self, key: &str) -> Result<Shape, E> {
+        validate_short_key(key).map_err(|errors| {
+            let errors = errors
+                .iter()
+                .map(|err| format!("{err} (at column {})", err.column(key)))
+                .collect::<Vec<_>>();
+            E::custom(format_args!(
+                "invalid shape key \"{key}\": {}",
+                errors.join("; ")
+            ))
+        })
+    }
+}
+
+/// A structured representation of shapes, as a list of layers from bottom to top,
+/// each a list of its quads written as in a short key.
+///
+/// Use it on fields through `#[serde(with = "shapez_core::structured")]`.
+/// The shape `"RuCw--Cw:----Ru--"` is written as
+/// `[["Ru", "Cw", "--", "Cw"], ["--", "--", "Ru", "--"]]`,
+/// which keeps every quad on its own in formats without `null`, like TOML.
+///
+/// Deserializing goes through the same checks as a short key,
+/// so errors name the layer and quad they were found in.
+///
+/// # Example
+///
+/// ```
+/// use serde::Deserialize;
+/// use shapez_core::Shape;
+///
+/// #[derive(Debug, Deserialize)]
+/// struct Level {
+///     #[serde(with = "shapez_core::structured")]
+///     goal: Shape,
+/// }
+///
+/// let level: Level =
+///     serde_json::from_str(r#"{"goal": [["Ru", "Cw", "--", "Cw"], ["--", "--", "Ru", "--"]]}"#)
+///         .unwrap();
+/// assert_eq!(level.goal.to_short_key(), "RuCw--Cw:----Ru--");
+///
+/// let err = serde_json::from_str::<Level>(r#"{"goal": [["Ru", "Cx", "--", "Cw"]]}"#).unwrap_err();
+/// assert!(err.to_string().starts_with(
+///     "layer 1, quad 2: 'x' is not a valid color (expected one of r g b y p c w u)"
+/// ));
+/// ```
+pub mod structured {
+    use super::*;
+
+    /// Serializes the shape as a list of layers, each a list of its quads.
+    pub fn serialize<S: Serializer>(shape: &Shape, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(shape.layers.iter().map(|layer| layer.map(quad_key)))
+    }
+
+    /// Deserializes a list of layers, each a list of its quads, checking them like a short key.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Shape, D::Error> {
+        let layers = Vec::<Vec<String>>::deserialize(deserializer)?;
+        if layers.is_empty() {
+            return Err(de::Error::invalid_length(0, &"at least one layer"));
+        }
+
+        // Layers and quads of any other length would shift the quads after them
+        for (layer, quads) in layers.iter().enumerate() {
+            if quads.len() != QUADS_AMOUNT {
+                return Err(de::Error::custom(format_args!(
+                    "layer {}: expected {QUADS_AMOUNT} quads, found {}",
+                    layer + 1,
+                    quads.len()
+                )));
+            }
+            for (quad, key) in quads.iter().enumerate() {
+                if key.chars().count() != 2 {
+                    return Err(de::Error::custom(format_args!(
+                        "layer {}, quad {}: \"{key}\" is not a quad (expected 2 characters)",
+                        layer + 1,
+                        quad + 1
+                    )));
+                }
+            }
+        }
+
+        // Each layer is checked on its own, so no quad can shift the others into another layer
+        let layers = layers
+            .iter()
+            .map(|quads| quads.concat())
+            .collect::<Vec<_>>();
+        let mut errors = vec![];
+        let shape = Shape {
+            layers: check_short_layers(&layers, &mut errors),
+        };
+
+        if !errors.is_empty() {
+            let errors = errors.iter().map(ShapeKeyError::to_string);
+            return Err(de::Error::custom(errors.collect::<Vec<_>>().join("; ")));
+        }
+
+        Ok(shape)
+    }
+
+    fn quad_key(quad: Option<Quad>) -> String {
+        match quad {
+            Some(Quad(sub_shape, color)) => {
+                format!("{}{}", sub_shape.short_key(), color.short_key())
+            }
+            None => "--".to_string(),
+        }
+    }
+}
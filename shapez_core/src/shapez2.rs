@@ -0,0 +1,392 @@
+//! Types for the [shapez 2](https://shapez2.com) short-key dialect.
+//!
+//! Next to regular shapes, shapez 2 keys contain pins (`P-`) and crystals (`c` followed by a color),
+//! and use their own set of sub-shapes and colors.
+//! These are the types the `shapez2_shape!` macro from `shapez_macro` expands to.
+//!
+//! Hexagonal shapes have [`HEX_PARTS`] parts per layer and their own sub-shapes,
+//! they are represented by [`HexShape`], with parts of [`HexSubshape`]s.
+//!
+//! # Example
+//!
+//! ```
+//! use shapez_core::shapez2::{Color, HexShape, HexSubshape, Part, Shape, Subshape};
+//!
+//! let shape: Shape = "CuRm----:P-crP---".parse().unwrap();
+//! assert_eq!(shape.layers[0][1], Some(Part::Shape(Subshape::Square, Color::Magenta)));
+//! assert_eq!(shape.layers[1][1], Some(Part::Crystal(Color::Red)));
+//!
+//! let hex: HexShape = "HuFgGb------".parse().unwrap();
+//! assert_eq!(hex.layers[0][2], Some(Part::Shape(HexSubshape::Gear, Color::Blue)));
+//! ```
+
+use crate::parse::check_key;
+use crate::{MAX_LAYERS, QUADS_AMOUNT, ShapeKeyError};
+use std::fmt;
+use std::str::FromStr;
+
+/// The amount of parts in a single layer of a hexagonal shape.
+pub const HEX_PARTS: usize = 6;
+
+/// A single layer of a shapez 2 shape, with `PARTS` parts of the sub-shapes `S`.
+///
+/// Parts are ordered clockwise, starting from the top right one.
+/// An empty part is represented by `None`.
+pub type Layer<const PARTS: usize = QUADS_AMOUNT, S = Subshape> = [Option<Part<S>>; PARTS];
+
+/// A shapez 2 shape, made of up to [`MAX_LAYERS`] layers stacked from bottom to top.
+///
+/// Regular shapes have [`QUADS_AMOUNT`] parts per layer, hexagonal ones have [`HEX_PARTS`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Shape<const PARTS: usize = QUADS_AMOUNT, S = Subshape> {
+    pub layers: Vec<Layer<PARTS, S>>,
+}
+
+/// A hexagonal shapez 2 shape, with [`HEX_PARTS`] parts per layer.
+pub type HexShape = Shape<HEX_PARTS, HexSubshape>;
+
+/// A single part of a layer, with the sub-shapes `S`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Part<S = Subshape> {
+    /// A sub-shape with a color, e.g. `Cu` in a short key
+    Shape(S, Color),
+    /// `P-` in a short key
+    Pin,
+    /// `c` followed by a color in a short key
+    Crystal(Color),
+}
+
+/// The sub-shape of a part of a regular shapez 2 shape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Subshape {
+    /// `C` in a short key
+    Circle,
+    /// `R` in a short key
+    Square,
+    /// `S` in a short key
+    Star,
+    /// `W` in a short key
+    Diamond,
+}
+
+/// The sub-shape of a part of a hexagonal shapez 2 shape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HexSubshape {
+    /// `H` in a hexagonal short key
+    Hexagon,
+    /// `F` in a hexagonal short key
+    Flower,
+    /// `G` in a hexagonal short key
+    Gear,
+}
+
+/// The color of a shapez 2 part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    /// `r` in a short key
+    Red,
+    /// `g` in a short key
+    Green,
+    /// `b` in a short key
+    Blue,
+    /// `c` in a short key
+    Cyan,
+    /// `m` in a short key
+    Magenta,
+    /// `y` in a short key
+    Yellow,
+    /// `w` in a short key
+    White,
+    /// `u` in a short key
+    Uncolored,
+}
+
+const SUB_SHAPES: &str = "C R S W P c";
+const HEX_SUB_SHAPES: &str = "H F G P c";
+const COLORS: &str = "r g b c m y w u";
+
+fn get_sub_shape(sub_shape: char) -> Option<Subshape> {
+    match sub_shape {
+        'C' => Some(Subshape::Circle),
+        'R' => Some(Subshape::Square),
+        'S' => Some(Subshape::Star),
+        'W' => Some(Subshape::Diamond),
+        _ => None,
+    }
+}
+
+fn get_hex_sub_shape(sub_shape: char) -> Option<HexSubshape> {
+    match sub_shape {
+        'H' => Some(HexSubshape::Hexagon),
+        'F' => Some(HexSubshape::Flower),
+        'G' => Some(HexSubshape::Gear),
+        _ => None,
+    }
+}
+
+fn get_color(color: char) -> Option<Color> {
+    match color {
+        'r' => Some(Color::Red),
+        'g' => Some(Color::Green),
+        'b' => Some(Color::Blue),
+        'c' => Some(Color::Cyan),
+        'm' => Some(Color::Magenta),
+        'y' => Some(Color::Yellow),
+        'w' => Some(Color::White),
+        'u' => Some(Color::Uncolored),
+        _ => None,
+    }
+}
+
+fn check_color(
+    layer: usize,
+    quad: usize,
+    color: char,
+    errors: &mut Vec<ShapeKeyError>,
+) -> Option<Color> {
+    get_color(color).or_else(|| {
+        errors.push(ShapeKeyError::InvalidColor {
+            layer,
+            quad,
+            found: color,
+            expected: COLORS,
+        });
+        None
+    })
+}
+
+fn check_part(
+    layer: usize,
+    quad: usize,
+    chars: [char; 2],
+    errors: &mut Vec<ShapeKeyError>,
+) -> Option<Part> {
+    check_part_with(layer, quad, chars, errors, get_sub_shape, SUB_SHAPES)
+}
+
+fn check_hex_part(
+    layer: usize,
+    quad: usize,
+    chars: [char; 2],
+    errors: &mut Vec<ShapeKeyError>,
+) -> Option<Part<HexSubshape>> {
+    check_part_with(
+        layer,
+        quad,
+        chars,
+        errors,
+        get_hex_sub_shape,
+        HEX_SUB_SHAPES,
+    )
+}
+
+fn check_part_with<S>(
+    layer: usize,
+    quad: usize,
+    chars: [char; 2],
+    errors: &mut Vec<ShapeKeyError>,
+    get_sub_shape: fn(char) -> Option<S>,
+    expected: &'static str,
+) -> Option<Part<S>> {
+    let [sub_shape, color] = chars;
+
+    match (sub_shape, color) {
+        // Check for "--"
+        ('-', '-') => None,
+
+        // Pins never have a color
+        ('P', '-') => Some(Part::Pin),
+        ('P', found) => {
+            errors.push(ShapeKeyError::ColoredPin { layer, quad, found });
+            None
+        }
+
+        ('c', color) => Some(Part::Crystal(check_color(layer, quad, color, errors)?)),
+
+        // Ensure the sub-shape and color are valid, reporting both if neither is
+        (sub_shape, color) => {
+            let sub_shape = get_sub_shape(sub_shape).or_else(|| {
+                errors.push(ShapeKeyError::InvalidSubShape {
+                    layer,
+                    quad,
+                    found: sub_shape,
+                    expected,
+                });
+                None
+            });
+            let color = check_color(layer, quad, color, errors);
+
+            Some(Part::Shape(sub_shape?, color?))
+        }
+    }
+}
+
+/// Parses a shapez 2 short key like `"CuRm----:P-crP---"` into a [`Shape`],
+/// collecting every problem of the key.
+///
+/// This is the validator `shapez2_shape!` runs at compile time.
+/// The layer structure follows the same rules as [`validate_short_key`](crate::validate_short_key).
+pub fn validate_short_key(key: &str) -> Result<Shape, Vec<ShapeKeyError>> {
+    validate_short_key_with_max_layers(key, MAX_LAYERS)
+}
+
+/// Same as [`validate_short_key`], but accepting up to `max_layers` layers instead of [`MAX_LAYERS`].
+pub fn validate_short_key_with_max_layers(
+    key: &str,
+    max_layers: usize,
+) -> Result<Shape, Vec<ShapeKeyError>> {
+    let layers = check_key(key, max_layers, "parts", check_part)?;
+    Ok(Shape { layers })
+}
+
+/// Parses a shapez 2 short key like `"CuRm----:P-crP---"` into a [`Shape`],
+/// stopping at the first problem of the key.
+pub fn parse_short_key(key: &str) -> Result<Shape, ShapeKeyError> {
+    validate_short_key(key).map_err(|mut errors| errors.swap_remove(0))
+}
+
+impl FromStr for Shape {
+    type Err = ShapeKeyError;
+
+    fn from_str(key: &str) -> Result<Self, Self::Err> {
+        parse_short_key(key)
+    }
+}
+
+/// Parses a hexagonal shapez 2 short key like `"HuFuGu------:P-----------"` into a [`HexShape`],
+/// collecting every problem of the key.
+///
+/// Each layer takes [`HEX_PARTS`] parts, using the [`HexSubshape`]s (H, F, or G),
+/// pins and crystals. The sub-shapes of regular shapes are rejected.
+///
+/// This is the validator `shapez2_hex_shape!` runs at compile time.
+///
+/// # Example
+///
+/// ```
+/// use shapez_core::shapez2::validate_hex_short_key;
+///
+/// let errors = validate_hex_short_key("HuFuGu------:HuFu").unwrap_err();
+/// assert_eq!(errors[0].to_string(), "layer 2: expected 6 parts (12 characters)");
+/// ```
+pub fn validate_hex_short_key(key: &str) -> Result<HexShape, Vec<ShapeKeyError>> {
+    validate_hex_short_key_with_max_layers(key, MAX_LAYERS)
+}
+
+/// Same as [`validate_hex_short_key`], but accepting up to `max_layers` layers instead of [`MAX_LAYERS`].
+pub fn validate_hex_short_key_with_max_layers(
+    key: &str,
+    max_layers: usize,
+) -> Result<HexShape, Vec<ShapeKeyError>> {
+    let layers = check_key(key, max_layers, "parts", check_hex_part)?;
+    Ok(Shape { layers })
+}
+
+/// Parses a hexagonal shapez 2 short key like `"HuFuGu------:P-----------"` into a [`HexShape`],
+/// stopping at the first problem of the key.
+pub fn parse_hex_short_key(key: &str) -> Result<HexShape, ShapeKeyError> {
+    validate_hex_short_key(key).map_err(|mut errors| errors.swap_remove(0))
+}
+
+impl FromStr for HexShape {
+    type Err = ShapeKeyError;
+
+    fn from_str(key: &str) -> Result<Self, Self::Err> {
+        parse_hex_short_key(key)
+    }
+}
+
+impl Subshape {
+    /// Returns the character representing this sub-shape in a short key.
+    pub fn short_key(self) -> char {
+        match self {
+            Self::Circle => 'C',
+            Self::Square => 'R',
+            Self::Star => 'S',
+            Self::Diamond => 'W',
+        }
+    }
+}
+
+impl HexSubshape {
+    /// Returns the character representing this sub-shape in a hexagonal short key.
+    pub fn short_key(self) -> char {
+        match self {
+            Self::Hexagon => 'H',
+            Self::Flower => 'F',
+            Self::Gear => 'G',
+        }
+    }
+}
+
+impl Color {
+    /// Returns the character representing this color in a short key.
+    pub fn short_key(self) -> char {
+        match self {
+            Self::Red => 'r',
+            Self::Green => 'g',
+            Self::Blue => 'b',
+            Self::Cyan => 'c',
+            Self::Magenta => 'm',
+            Self::Yellow => 'y',
+            Self::White => 'w',
+            Self::Uncolored => 'u',
+        }
+    }
+}
+
+impl<const PARTS: usize> Shape<PARTS> {
+    /// Returns the canonical short key of this shape, like `"CuRm----:P-crP---"`.
+    ///
+    /// This is the exact inverse of [`parse_short_key`].
+    pub fn to_short_key(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl HexShape {
+    /// Returns the canonical short key of this shape, like `"HuFuGu------:P-----------"`.
+    ///
+    /// This is the exact inverse of [`parse_hex_short_key`].
+    pub fn to_short_key(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl<const PARTS: usize> fmt::Display for Shape<PARTS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_layers(f, &self.layers, Subshape::short_key)
+    }
+}
+
+impl fmt::Display for HexShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_layers(f, &self.layers, HexSubshape::short_key)
+    }
+}
+
+/// Writes the layers as a short key, given the character of each sub-shape.
+fn write_layers<const PARTS: usize, S: Copy>(
+    f: &mut fmt::Formatter<'_>,
+    layers: &[Layer<PARTS, S>],
+    short_key: fn(S) -> char,
+) -> fmt::Result {
+    for (index, layer) in layers.iter().enumerate() {
+        if index > 0 {
+            f.write_str(":")?;
+        }
+
+        for part in layer {
+            match part {
+                Some(Part::Shape(sub_shape, color)) => {
+                    write!(f, "{}{}", short_key(*sub_shape), color.short_key())?
+                }
+                Some(Part::Pin) => f.write_str("P-")?,
+                Some(Part::Crystal(color)) => write!(f, "c{}", color.short_key())?,
+                None => f.write_str("--")?,
+            }
+        }
+    }
+
+    Ok(())
+}
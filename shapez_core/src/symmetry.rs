@@ -0,0 +1,104 @@
+use crate::{QUADS_AMOUNT, Quad, Shape, Subshape};
+
+/// An axis a shape can be mirrored along, through the center of the shape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis {
+    /// The axis the cutter cuts along, swapping the west and east halves.
+    Vertical,
+    /// The axis swapping the top and bottom halves.
+    Horizontal,
+    /// The axis through the top right and bottom left quads.
+    Diagonal,
+    /// The axis through the top left and bottom right quads.
+    AntiDiagonal,
+}
+
+impl Axis {
+    /// Returns the quad `quad` ends up at when mirrored along this axis.
+    fn mirror(self, quad: usize) -> usize {
+        match self {
+            Self::Vertical => QUADS_AMOUNT - 1 - quad,
+            Self::Horizontal => quad ^ 1,
+            Self::Diagonal => (QUADS_AMOUNT - quad) % QUADS_AMOUNT,
+            Self::AntiDiagonal => (QUADS_AMOUNT + 2 - quad) % QUADS_AMOUNT,
+        }
+    }
+}
+
+impl Shape {
+    /// Returns whether the shape looks the same after rotating it by a fraction `1 / order` of a turn.
+    ///
+    /// Every shape is symmetric of order 1, an order of 4 means every rotation looks the same,
+    /// so the shape never needs a rotator.
+    ///
+    /// Quads can only be rotated by whole quads, so every other order is never symmetric,
+    /// including 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use shapez_core::Shape;
+    ///
+    /// let shape: Shape = "CuRuCuRu".parse().unwrap();
+    /// assert!(shape.is_rotationally_symmetric(2));
+    /// assert!(!shape.is_rotationally_symmetric(4));
+    /// assert!(!shape.is_rotationally_symmetric(3));
+    /// ```
+    pub fn is_rotationally_symmetric(&self, order: usize) -> bool {
+        if order == 0 || !QUADS_AMOUNT.is_multiple_of(order) {
+            return false;
+        }
+
+        let steps = QUADS_AMOUNT / order;
+        self.layers.iter().all(|layer| {
+            let mut rotated = *layer;
+            rotated.rotate_right(steps);
+            rotated == *layer
+        })
+    }
+
+    /// Returns whether the shape looks the same after mirroring it along `axis`.
+    ///
+    /// Windmills have no mirror image among the sub-shapes, so a shape containing one never is.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use shapez_core::{Axis, Shape};
+    ///
+    /// let shape: Shape = "CuCu----:RrRr----".parse().unwrap();
+    /// assert!(shape.is_mirror_symmetric(Axis::Horizontal));
+    /// assert!(!shape.is_mirror_symmetric(Axis::Vertical));
+    ///
+    /// let shape: Shape = "WuWuWuWu".parse().unwrap();
+    /// assert!(!shape.is_mirror_symmetric(Axis::Vertical));
+    /// ```
+    pub fn is_mirror_symmetric(&self, axis: Axis) -> bool {
+        self.layers.iter().all(|layer| {
+            (0..QUADS_AMOUNT).all(|quad| match layer[quad] {
+                Some(Quad(Subshape::Windmill, _)) => false,
+                current => layer[axis.mirror(quad)] == current,
+            })
+        })
+    }
+}
+
+/// Returns whether `a` and `b` are the same shape, up to a rotation of one of them.
+///
+/// # Example
+///
+/// ```
+/// use shapez_core::{Shape, equivalent_up_to_rotation};
+///
+/// let a: Shape = "CuRu----".parse().unwrap();
+/// let b: Shape = "--CuRu--".parse().unwrap();
+/// assert!(equivalent_up_to_rotation(&a, &b));
+///
+/// let c: Shape = "RuCu----".parse().unwrap();
+/// assert!(!equivalent_up_to_rotation(&a, &c));
+/// ```
+pub fn equivalent_up_to_rotation(a: &Shape, b: &Shape) -> bool {
+    std::iter::successors(Some(b.clone()), |shape| Some(shape.rotate_cw()))
+        .take(QUADS_AMOUNT)
+        .any(|rotation| rotation == *a)
+}
@@ -0,0 +1,54 @@
+use shapez_core::{Shape, StaticShape, assert_shape_eq};
+use shapez_macro::{shapez_shape, shapez_shape_const};
+
+#[test]
+fn layers_are_drawn_side_by_side() {
+    let shape = shapez_shape!("CrSgRbWy:Cu------:--Cu----");
+    assert_eq!(
+        shape.to_ascii(),
+        "Wy Cr | -- Cu | -- --\nRb Sg | -- -- | -- Cu"
+    );
+    assert_eq!(Shape { layers: vec![] }.to_ascii(), "");
+    assert_eq!(Shape { layers: vec![] }.to_ansi(), "");
+}
+
+#[test]
+fn ansi_colors_every_quad() {
+    let shape = shapez_shape!("CuCwCpCy");
+    assert_eq!(
+        shape.to_ansi(),
+        "\x1b[33mCy\x1b[0m \x1b[90mCu\x1b[0m\n\x1b[35mCp\x1b[0m \x1b[97mCw\x1b[0m"
+    );
+}
+
+#[test]
+fn equal_shapes_pass() {
+    const GOAL: StaticShape = shapez_shape_const!("CuCu----");
+    assert_shape_eq!(shapez_shape!("----CuCu").rotate_180(), GOAL);
+}
+
+#[test]
+#[should_panic(
+    expected = "failed\n  left: CuCu----\n right: CuCr----\n left: -- Cu\n       -- Cu\nright: -- Cu\n       -- Cr\n diff:\n          ^^"
+)]
+fn differing_shapes_show_a_diff() {
+    assert_shape_eq!(shapez_shape!("CuCu----"), shapez_shape!("CuCr----"));
+}
+
+#[test]
+fn diff_marks_changed_quads() {
+    let left = shapez_shape!("CuCu----:Ru------");
+    let right = shapez_shape!("CuCu----:--Ru----:Sg------");
+    assert_eq!(
+        left.ascii_diff(&right),
+        [
+            " left: -- Cu | -- Ru |",
+            "       -- Cu | -- -- |",
+            "right: -- Cu | -- -- | -- Sg",
+            "       -- Cu | -- Ru | -- --",
+            " diff:       |    ^^ |    ^^",
+            "             |    ^^ |",
+        ]
+        .join("\n")
+    );
+}
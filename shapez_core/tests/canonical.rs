@@ -0,0 +1,55 @@
+mod common;
+
+use common::{quad_of, shape_of};
+use proptest::prelude::*;
+use shapez_core::{Color, Shape, Subshape};
+use shapez_macro::{shapez_canonical, shapez_shape};
+
+fn shape() -> impl Strategy<Value = Shape> {
+    let sub_shape = prop_oneof![Just(Subshape::Circle), Just(Subshape::Rectangle)];
+    let color = prop_oneof![Just(Color::Red), Just(Color::Uncolored)];
+    shape_of(quad_of(sub_shape, color))
+}
+
+proptest! {
+    #[test]
+    fn rotations_share_the_canonical_rotation(shape in shape()) {
+        let canonical = shape.canonical_rotation();
+        prop_assert_eq!(shape.rotate_cw().canonical_rotation(), canonical.clone());
+        prop_assert_eq!(shape.rotate_180().canonical_rotation(), canonical.clone());
+        prop_assert_eq!(shape.rotate_ccw().canonical_rotation(), canonical.clone());
+        prop_assert_eq!(canonical.canonical_rotation(), canonical);
+    }
+
+    #[test]
+    fn canonical_rotation_has_the_smallest_key(shape in shape()) {
+        let key = shape.canonical_rotation().to_short_key();
+        prop_assert!(key <= shape.to_short_key());
+        prop_assert!(key <= shape.rotate_cw().to_short_key());
+        prop_assert!(key <= shape.rotate_180().to_short_key());
+        prop_assert!(key <= shape.rotate_ccw().to_short_key());
+    }
+}
+
+#[test]
+fn normalize_drops_empty_top_layers_only() {
+    let mut shape = shapez_shape!("CuCu----:Ru------");
+    shape.layers.push([None; 4]);
+    shape.layers.push([None; 4]);
+    assert_eq!(shape.normalize(), shapez_shape!("CuCu----:Ru------"));
+
+    let empty = Shape {
+        layers: vec![[None; 4]],
+    };
+    assert!(empty.normalize().is_empty());
+}
+
+#[test]
+fn macro_expands_to_the_canonical_rotation() {
+    assert_eq!(
+        shapez_canonical!("CrCuCuCu"),
+        shapez_shape!("CrCuCuCu").canonical_rotation()
+    );
+    assert_eq!(shapez_canonical!("CuCuCuCr"), shapez_canonical!("CrCuCuCu"));
+    assert_eq!(shapez_canonical!("CuCuCuCu"), shapez_shape!("CuCuCuCu"));
+}
@@ -0,0 +1,53 @@
+//! Proptest strategies shared by the integration tests.
+
+// Every test crate only uses some of the strategies
+#![allow(dead_code)]
+
+use proptest::prelude::*;
+use shapez_core::{Color, Layer, MAX_LAYERS, Quad, Shape, Subshape};
+
+pub fn sub_shape() -> impl Strategy<Value = Subshape> + Clone {
+    prop_oneof![
+        Just(Subshape::Circle),
+        Just(Subshape::Square),
+        Just(Subshape::Rectangle),
+        Just(Subshape::Windmill),
+    ]
+}
+
+pub fn color() -> impl Strategy<Value = Color> + Clone {
+    prop_oneof![
+        Just(Color::Red),
+        Just(Color::Green),
+        Just(Color::Blue),
+        Just(Color::Yellow),
+        Just(Color::Purple),
+        Just(Color::Cyan),
+        Just(Color::White),
+        Just(Color::Uncolored),
+    ]
+}
+
+/// Quads made of the given sub-shapes and colors.
+pub fn quad_of(
+    sub_shape: impl Strategy<Value = Subshape> + Clone,
+    color: impl Strategy<Value = Color> + Clone,
+) -> impl Strategy<Value = Quad> + Clone {
+    (sub_shape, color).prop_map(|(s, c)| Quad(s, c))
+}
+
+/// Non-empty layers of the given quads.
+pub fn layer_of(quad: impl Strategy<Value = Quad> + Clone) -> impl Strategy<Value = Layer> {
+    proptest::array::uniform4(proptest::option::of(quad))
+        .prop_filter("empty layer", |layer| layer.iter().any(Option::is_some))
+}
+
+/// Shapes of up to [`MAX_LAYERS`] non-empty layers of the given quads.
+pub fn shape_of(quad: impl Strategy<Value = Quad> + Clone) -> impl Strategy<Value = Shape> {
+    proptest::collection::vec(layer_of(quad), 1..=MAX_LAYERS).prop_map(|layers| Shape { layers })
+}
+
+/// Any valid shape.
+pub fn shape() -> impl Strategy<Value = Shape> {
+    shape_of(quad_of(sub_shape(), color()))
+}
@@ -0,0 +1,46 @@
+use shapez_core::Color;
+use shapez_macro::shapez_mix;
+
+const COLORS: &str = "rgbypcwu";
+
+// The mixer table of shapez, with a row and a column for each color of `COLORS`
+const TABLE: [&str; 8] = [
+    "rypypwwr", // red
+    "ygcywcwg", // green
+    "pcbwpcwb", // blue
+    "yywywwwy", // yellow
+    "pwpwpwwp", // purple
+    "wccwwcwc", // cyan
+    "wwwwwwww", // white
+    "rgbypcwu", // uncolored
+];
+
+fn color(key: char) -> Color {
+    Color::from_short_key(key).unwrap()
+}
+
+#[test]
+fn mixing_follows_the_full_table() {
+    for (a, row) in COLORS.chars().zip(TABLE) {
+        for (b, expected) in COLORS.chars().zip(row.chars()) {
+            assert_eq!(color(a).mix(color(b)), color(expected), "{a} + {b}");
+        }
+    }
+}
+
+#[test]
+fn mixing_is_commutative() {
+    for a in COLORS.chars() {
+        for b in COLORS.chars() {
+            assert_eq!(color(a).mix(color(b)), color(b).mix(color(a)), "{a} + {b}");
+        }
+    }
+}
+
+#[test]
+fn macro_output_matches_mix() {
+    assert_eq!(shapez_mix!('r', 'g'), Color::Red.mix(Color::Green));
+    assert_eq!(shapez_mix!('b', 'y'), Color::Blue.mix(Color::Yellow));
+    assert_eq!(shapez_mix!('c', 'w'), Color::Cyan.mix(Color::White));
+    assert_eq!(shapez_mix!('p', 'u'), Color::Purple.mix(Color::Uncolored));
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use common::{quad_of, shape_of, sub_shape};
+use proptest::prelude::*;
+use shapez_core::{
+    Color, RecipeError, Shape, ShapeKeyError, find_recipe, validate_buildable_short_key,
+};
+use shapez_macro::shapez_shape;
+
+fn shape() -> impl Strategy<Value = Shape> {
+    let color = prop_oneof![
+        Just(Color::Red),
+        Just(Color::Green),
+        Just(Color::Blue),
+        Just(Color::Uncolored),
+    ];
+    shape_of(quad_of(sub_shape(), color))
+}
+
+proptest! {
+    #[test]
+    fn found_recipes_build_the_target(target in shape()) {
+        match find_recipe(&target) {
+            Ok(recipe) => prop_assert_eq!(recipe.evaluate(), target),
+            Err(RecipeError::Floating { layers }) => prop_assert!(!layers.is_empty()),
+            Err(err) => prop_assert!(false, "unexpected error: {}", err),
+        }
+    }
+}
+
+#[test]
+fn layers_resting_on_each_other_are_stacked() {
+    let target: Shape = "CuCuCuCu:RrRr----:--SgSg--:----WbWb".parse().unwrap();
+    assert_eq!(find_recipe(&target).unwrap().evaluate(), target);
+}
+
+#[test]
+fn floating_layers_are_held_up_by_a_cut_away_half() {
+    let target: Shape = "--CuCu--:Cu----Cu".parse().unwrap();
+    assert_eq!(find_recipe(&target).unwrap().evaluate(), target);
+}
+
+#[test]
+fn floating_layers_are_held_up_by_cut_off_layers() {
+    // The last layer lands on the third layer while the top holding it up is cut off by the stacker
+    let target: Shape = "--Cu----:--Cu----:----Cu--:CuCu----".parse().unwrap();
+    assert_eq!(find_recipe(&target).unwrap().evaluate(), target);
+}
+
+#[test]
+fn floating_layers_without_support_are_explained() {
+    let target: Shape = "Cu------:--Cu----:----Cu--:------Cu".parse().unwrap();
+    assert_eq!(
+        find_recipe(&target),
+        Err(RecipeError::Floating {
+            layers: vec![2, 3, 4]
+        })
+    );
+
+    let target = Shape {
+        layers: vec![[None; 4]],
+    };
+    assert_eq!(
+        find_recipe(&target),
+        Err(RecipeError::EmptyLayer { layer: 1 })
+    );
+}
+
+#[test]
+fn strict_keys_point_at_unsupported_quads() {
+    let key = "Cu------:--CuCu--";
+    let errors = validate_buildable_short_key(key).unwrap_err();
+    assert_eq!(
+        errors,
+        vec![
+            ShapeKeyError::UnsupportedQuad { layer: 2, quad: 2 },
+            ShapeKeyError::UnsupportedQuad { layer: 2, quad: 3 },
+        ]
+    );
+    assert_eq!(&key[errors[0].span(key)], "Cu");
+    assert_eq!(errors[1].column(key), 14);
+
+    assert_eq!(
+        validate_buildable_short_key("CuCu----:--Cu----"),
+        Ok(shapez_shape!(strict, "CuCu----:--Cu----"))
+    );
+}
@@ -0,0 +1,125 @@
+use shapez_core::Shape;
+use shapez_core::render::{self, RenderOptions};
+use std::path::Path;
+
+/// Compares `output` with the golden file `name`, rewriting it instead when `BLESS` is set.
+fn assert_golden(name: &str, output: &[u8]) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(name);
+    if std::env::var_os("BLESS").is_some() {
+        std::fs::write(&path, output).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read(&path)
+        .unwrap_or_else(|err| panic!("{}: {err}, run with BLESS=1 to create it", path.display()));
+    // Show a readable diff for text files
+    match (std::str::from_utf8(output), std::str::from_utf8(&expected)) {
+        (Ok(output), Ok(expected)) => {
+            assert_eq!(
+                output, expected,
+                "{name} differs, run with BLESS=1 to update it"
+            )
+        }
+        _ => assert!(
+            output == expected,
+            "{name} differs, run with BLESS=1 to update it"
+        ),
+    }
+}
+
+fn shape(key: &str) -> Shape {
+    key.parse().unwrap()
+}
+
+fn small(size: u32) -> RenderOptions {
+    RenderOptions {
+        size,
+        shadow: false,
+        background: None,
+    }
+}
+
+#[test]
+fn every_sub_shape_and_color() {
+    let svg = render::svg(&shape("CrRgSbWy:CpRcSwWu"), RenderOptions::default());
+    assert_golden("sub_shapes.svg", svg.as_bytes());
+}
+
+#[test]
+fn layers_are_scaled_down() {
+    let shape = shape("CuCuCuCu:RrRrRrRr:SgSgSgSg:WbWbWbWb");
+    let svg = render::svg(&shape, RenderOptions::default());
+    assert_golden("layers.svg", svg.as_bytes());
+}
+
+#[test]
+fn empty_quads_and_options() {
+    let svg = render::svg(&shape("RuCw--Cw:----Ru--"), small(64));
+    assert_golden("logo.svg", svg.as_bytes());
+
+    let options = RenderOptions {
+        background: Some([0x28, 0x32, 0x41]),
+        ..small(64)
+    };
+    let svg = render::svg(&shape("RuCw--Cw:----Ru--"), options);
+    assert_golden("background.svg", svg.as_bytes());
+}
+
+#[test]
+fn png_icons() {
+    let options = RenderOptions {
+        size: 64,
+        ..RenderOptions::default()
+    };
+    let png = render::png(&shape("RuCw--Cw:----Ru--"), options);
+    assert_golden("logo.png", &png);
+}
+
+#[test]
+fn png_header_follows_the_size() {
+    for size in [1, 7, 100] {
+        let png = render::png(&shape("CuCuCuCu"), small(size));
+        assert_eq!(png[..8], *b"\x89PNG\r\n\x1a\n");
+        assert_eq!(png[12..16], *b"IHDR");
+        assert_eq!(png[16..20], size.to_be_bytes());
+        assert_eq!(png[20..24], size.to_be_bytes());
+        assert_eq!(png[png.len() - 12..png.len() - 4], *b"\0\0\0\0IEND");
+    }
+}
+
+#[test]
+#[should_panic(expected = "size 0")]
+fn png_rejects_empty_images() {
+    render::png(&shape("CuCuCuCu"), small(0));
+}
+
+#[test]
+fn edges_are_anti_aliased() {
+    let pixels = render::rgba(&shape("CrCrCrCr"), small(50));
+    assert_eq!(pixels.len(), 50 * 50 * 4);
+
+    let alphas = pixels.chunks(4).map(|pixel| pixel[3]).collect::<Vec<_>>();
+    assert!(alphas.contains(&0));
+    assert!(alphas.contains(&255));
+    assert!(alphas.iter().any(|&alpha| alpha > 0 && alpha < 255));
+}
+
+#[test]
+fn background_fills_the_whole_image() {
+    let options = RenderOptions {
+        background: Some([0x28, 0x32, 0x41]),
+        ..RenderOptions::default()
+    };
+    let pixels = render::rgba(
+        &shape("WuWuWuWu"),
+        RenderOptions {
+            size: 40,
+            ..options
+        },
+    );
+
+    assert!(pixels.chunks(4).all(|pixel| pixel[3] == 255));
+    assert_eq!(pixels[..4], [0x28, 0x32, 0x41, 0xff]);
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use common::shape;
+use proptest::prelude::*;
+use shapez_core::{Shape, shapez2};
+use shapez_macro::{shapez_shape, shapez2_hex_shape, shapez2_shape};
+
+proptest! {
+    #[test]
+    fn shape_round_trips_through_short_key(shape in shape()) {
+        let key = shape.to_short_key();
+        prop_assert_eq!(key.parse::<Shape>(), Ok(shape));
+    }
+
+    #[test]
+    fn valid_key_round_trips_through_shape(shape in shape()) {
+        let key = shape.to_short_key();
+        let parsed: Shape = key.parse().unwrap();
+        prop_assert_eq!(parsed.to_string(), key);
+    }
+}
+
+#[test]
+fn macro_output_round_trips() {
+    assert_eq!(
+        shapez_shape!("RuCw--Cw:----Ru--").to_string(),
+        "RuCw--Cw:----Ru--"
+    );
+    assert_eq!(
+        shapez_shape!("CrSgRbWy:CpCcCwCu").to_string(),
+        "CrSgRbWy:CpCcCwCu"
+    );
+    assert_eq!(
+        shapez_shape!("Cu------:--Cu----:----Cu--:------Cu").to_string(),
+        "Cu------:--Cu----:----Cu--:------Cu"
+    );
+}
+
+#[test]
+fn shapez2_keys_round_trip() {
+    for key in ["CuRm----:P-crP---", "SwWy----", "cucu--cu:P-P-P-P-"] {
+        let shape: shapez2::Shape = key.parse().unwrap();
+        assert_eq!(shape.to_string(), key);
+    }
+    assert_eq!(
+        shapez2_shape!("CuRm----:P-crP---").to_string(),
+        "CuRm----:P-crP---"
+    );
+}
+
+#[test]
+fn shapez2_hex_keys_round_trip() {
+    for key in ["HuFgGb------:P-----------", "cucu--cucu--"] {
+        let shape: shapez2::HexShape = key.parse().unwrap();
+        assert_eq!(shape.to_string(), key);
+    }
+    assert_eq!(
+        shapez2_hex_shape!("HuFgGb------:P-----------").to_string(),
+        "HuFgGb------:P-----------"
+    );
+    assert!("CuHu--------".parse::<shapez2::HexShape>().is_err());
+    assert!("CuHu----".parse::<shapez2::Shape>().is_err());
+}
+
+#[test]
+fn shapez2_colored_pins_are_rejected() {
+    let err = "CuPr----".parse::<shapez2::Shape>().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "layer 1, quad 2: pins have no color, write `P-` instead of `Pr`"
+    );
+    assert_eq!(err.column("CuPr----"), 4);
+}
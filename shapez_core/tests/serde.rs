@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+use shapez_core::{Shape, StaticShape};
+use shapez_macro::{shapez_shape, shapez_shape_const};
+
+/// A level file, storing its goal as a short key and its bonus as a list of layers.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Level {
+    goal: Shape,
+    #[serde(with = "shapez_core::structured")]
+    bonus: Shape,
+}
+
+fn level() -> Level {
+    Level {
+        goal: shapez_shape!("RuCw--Cw:----Ru--"),
+        bonus: shapez_shape!("CrCr----:--Rg--Rg"),
+    }
+}
+
+#[test]
+fn json_round_trip() {
+    let json = serde_json::to_string(&level()).unwrap();
+    assert_eq!(
+        json,
+        r#"{"goal":"RuCw--Cw:----Ru--","bonus":[["Cr","Cr","--","--"],["--","Rg","--","Rg"]]}"#
+    );
+    assert_eq!(serde_json::from_str::<Level>(&json).unwrap(), level());
+
+    const GOAL: StaticShape = shapez_shape_const!("CrCr----");
+    assert_eq!(serde_json::to_string(&GOAL).unwrap(), r#""CrCr----""#);
+}
+
+#[test]
+fn toml_round_trip() {
+    let toml = toml::to_string(&level()).unwrap();
+    assert_eq!(
+        toml,
+        r#"goal = "RuCw--Cw:----Ru--"
+bonus = [["Cr", "Cr", "--", "--"], ["--", "Rg", "--", "Rg"]]
+"#
+    );
+    assert_eq!(toml::from_str::<Level>(&toml).unwrap(), level());
+}
+
+#[test]
+fn short_key_errors_point_at_the_key() {
+    let err = serde_json::from_str::<Shape>(r#""RuCx--Cw""#).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid shape key \"RuCx--Cw\": layer 1, quad 2: 'x' is not a valid color \
+         (expected one of r g b y p c w u) (at column 4) at line 1 column 10"
+    );
+
+    let err = serde_json::from_str::<Shape>(r#""RxCw--Cw:--------""#).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid shape key \"RxCw--Cw:--------\": layer 1, quad 1: 'x' is not a valid color \
+         (expected one of r g b y p c w u) (at column 2); layer 2: every quad is empty (at column 10) \
+         at line 1 column 19"
+    );
+
+    let err = serde_json::from_str::<Shape>(r#""Cu------:Cu------:Cu------:Cu------:Cu------""#);
+    assert!(
+        err.unwrap_err()
+            .to_string()
+            .contains(": key has 5 layers, at most 4 are allowed (at column 37)")
+    );
+
+    let err = serde_json::from_str::<Shape>("5").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid type: integer `5`, expected a short shape key like \"RuCw--Cw:----Ru--\" \
+         at line 1 column 1"
+    );
+}
+
+#[test]
+fn toml_errors_point_at_the_field() {
+    let document = r#"goal = "RuCw--Cw:----Ru--"
+bonus = [["Cr", "Cr", "--", "--"], ["--", "Xg", "--", "Rg"]]
+"#;
+    let err = toml::from_str::<Level>(document).unwrap_err();
+    assert_eq!(
+        &document[err.span().unwrap()],
+        r#"[["Cr", "Cr", "--", "--"], ["--", "Xg", "--", "Rg"]]"#
+    );
+    assert_eq!(
+        err.message(),
+        "layer 2, quad 2: 'X' is not a valid sub-shape (expected one of C S R W)"
+    );
+}
+
+fn bonus(json: &str) -> String {
+    let json = format!(r#"{{"goal": "Cu------", "bonus": {json}}}"#);
+    let err = serde_json::from_str::<Level>(&json).unwrap_err();
+    // Drop the line and column, the message is about the whole field
+    err.to_string()
+        .split(" at line")
+        .next()
+        .unwrap()
+        .to_string()
+}
+
+#[test]
+fn structured_errors_name_the_quad() {
+    assert_eq!(
+        bonus(r#"[["Cu", "--", "--", "--"], ["--", "Xr", "--", "--"]]"#),
+        "layer 2, quad 2: 'X' is not a valid sub-shape (expected one of C S R W)"
+    );
+    // A separator in a quad stays in its quad rather than starting another layer
+    assert_eq!(
+        bonus(r#"[["C:", "--", "--", "--"], ["Cu", "--", "--", "--"]]"#),
+        "layer 1, quad 1: ':' is not a valid color (expected one of r g b y p c w u)"
+    );
+    assert_eq!(
+        bonus(r#"[["Cu", "Cur", "--", "--"]]"#),
+        "layer 1, quad 2: \"Cur\" is not a quad (expected 2 characters)"
+    );
+    assert_eq!(
+        bonus(r#"[["Cu", "--", "--"]]"#),
+        "layer 1: expected 4 quads, found 3"
+    );
+    assert_eq!(
+        bonus(r#"[["--", "--", "--", "--"]]"#),
+        "layer 1: every quad is empty"
+    );
+    assert_eq!(
+        bonus(&format!(
+            "[{}]",
+            [r#"["Cu", "--", "--", "--"]"#; 5].join(", ")
+        )),
+        "key has 5 layers, at most 4 are allowed"
+    );
+    assert_eq!(bonus("[]"), "invalid length 0, expected at least one layer");
+}
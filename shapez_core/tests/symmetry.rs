@@ -0,0 +1,67 @@
+use shapez_core::{Axis, Shape, equivalent_up_to_rotation};
+use shapez_macro::{shapez_assert_symmetric, shapez_shape};
+
+shapez_assert_symmetric!("CuCuCuCu:RrRrRrRr");
+shapez_assert_symmetric!("CuRuCuRu", order = 2);
+shapez_assert_symmetric!("Cu--Cu--", axis = diagonal);
+
+#[test]
+fn rotational_symmetry_follows_the_order() {
+    let shape = shapez_shape!("CuRuCuRu:Cr--Cr--");
+    assert!(shape.is_rotationally_symmetric(1));
+    assert!(shape.is_rotationally_symmetric(2));
+    assert!(!shape.is_rotationally_symmetric(4));
+
+    let shape = shapez_shape!("CuRu----");
+    assert!(shape.is_rotationally_symmetric(1));
+    assert!(!shape.is_rotationally_symmetric(2));
+}
+
+#[test]
+fn rotational_symmetry_needs_whole_quads() {
+    let shape = shapez_shape!("CuCuCuCu");
+    for order in [0, 3, 5, 8] {
+        assert!(!shape.is_rotationally_symmetric(order));
+    }
+}
+
+#[test]
+fn mirror_symmetry_follows_the_axis() {
+    let shape = shapez_shape!("Cu----Cu:Rr----Rr");
+    assert!(shape.is_mirror_symmetric(Axis::Vertical));
+    assert!(!shape.is_mirror_symmetric(Axis::Horizontal));
+
+    let shape = shapez_shape!("SrCu--Cu");
+    assert!(shape.is_mirror_symmetric(Axis::Diagonal));
+    assert!(!shape.is_mirror_symmetric(Axis::AntiDiagonal));
+
+    let shape = shapez_shape!("Cu--Cu--");
+    assert!(shape.is_mirror_symmetric(Axis::AntiDiagonal));
+    assert!(!shape.is_mirror_symmetric(Axis::Vertical));
+}
+
+#[test]
+fn windmills_are_never_mirror_symmetric() {
+    let shape = shapez_shape!("WuWuWuWu");
+    assert!(shape.is_rotationally_symmetric(4));
+    for axis in [
+        Axis::Vertical,
+        Axis::Horizontal,
+        Axis::Diagonal,
+        Axis::AntiDiagonal,
+    ] {
+        assert!(!shape.is_mirror_symmetric(axis));
+    }
+}
+
+#[test]
+fn equivalence_ignores_rotation_only() {
+    let a = shapez_shape!("CuRu----:Sg------");
+    assert!(equivalent_up_to_rotation(&a, &a.rotate_cw()));
+    assert!(equivalent_up_to_rotation(&a.rotate_180(), &a));
+    assert!(!equivalent_up_to_rotation(
+        &a,
+        &shapez_shape!("RuCu----:Sg------")
+    ));
+    assert!(!equivalent_up_to_rotation(&a, &Shape { layers: vec![] }));
+}
@@ -0,0 +1,362 @@
+use crate::tokens::Paths;
+use proc_macro2::Span;
+use quote::ToTokens;
+use shapez_core::MAX_LAYERS;
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::{Ident, Lit, LitChar, LitInt, LitStr, Token};
+
+/// The arguments of the shape macros: the literal inputs, optionally surrounded by options.
+///
+/// ```ignore
+/// shapez_shape!("Cu------:Cu------:Cu------:Cu------:Cu------", max_layers = 5)
+/// shapez_shape!(crate = my_engine::shapes, shape = ShapeDef, "RuCw--Cw")
+/// shapez_stack!("CuCuCuCu", "RuRuRuRu")
+/// shapez_paint!("CuCuCuCu", 'r')
+/// shapez_shape!(strict, "CuCuCuCu:--Cu----")
+/// ```
+pub(crate) struct ShapeArgs<T = LitStr> {
+    pub inputs: T,
+    pub max_layers: usize,
+    pub strict: bool,
+    pub types: TypeOverrides,
+}
+
+/// Every option, in the order they are listed in error messages.
+pub(crate) const ALL_OPTIONS: &[&str] = &[
+    "strict",
+    "max_layers",
+    "crate",
+    "shape",
+    "quad",
+    "subshape",
+    "color",
+];
+
+/// The literal inputs a macro expects, in order.
+pub(crate) trait Inputs: Sized {
+    /// Describes the expected inputs for error messages.
+    const EXPECTED: &'static str;
+    /// The options that have an effect on the macro, every other one is rejected.
+    const OPTIONS: &'static [&'static str] = ALL_OPTIONS;
+
+    fn from_literals(literals: Vec<Lit>) -> Option<Self>;
+}
+
+/// A single short key.
+impl Inputs for LitStr {
+    const EXPECTED: &'static str = "expected a short key";
+
+    fn from_literals(literals: Vec<Lit>) -> Option<Self> {
+        match <[Lit; 1]>::try_from(literals).ok()? {
+            [Lit::Str(key)] => Some(key),
+            _ => None,
+        }
+    }
+}
+
+/// A single shapez 2 short key, which has no rules for what the game can build.
+pub(crate) struct Shapez2Inputs {
+    pub key: LitStr,
+}
+
+impl Inputs for Shapez2Inputs {
+    const EXPECTED: &'static str = "expected a short key";
+    const OPTIONS: &'static [&'static str] =
+        &["max_layers", "crate", "shape", "quad", "subshape", "color"];
+
+    fn from_literals(literals: Vec<Lit>) -> Option<Self> {
+        LitStr::from_literals(literals).map(|key| Self { key })
+    }
+}
+
+/// The short key of `#[shapez_doc]`, which expands to no types.
+pub(crate) struct DocInputs {
+    pub key: LitStr,
+}
+
+impl Inputs for DocInputs {
+    const EXPECTED: &'static str = "expected a short key";
+    const OPTIONS: &'static [&'static str] = &["strict", "max_layers"];
+
+    fn from_literals(literals: Vec<Lit>) -> Option<Self> {
+        LitStr::from_literals(literals).map(|key| Self { key })
+    }
+}
+
+/// The short key of `shapez_recipe!`, whose solver is limited to the layers of the stacker
+/// and whose expansion only names the recipe, sub-shape and color types.
+pub(crate) struct RecipeInputs {
+    pub key: LitStr,
+}
+
+impl Inputs for RecipeInputs {
+    const EXPECTED: &'static str = "expected a short key";
+    const OPTIONS: &'static [&'static str] = &["strict", "crate", "subshape", "color"];
+
+    fn from_literals(literals: Vec<Lit>) -> Option<Self> {
+        LitStr::from_literals(literals).map(|key| Self { key })
+    }
+}
+
+/// The inputs of the stacker, a bottom and a top short key.
+pub(crate) struct StackInputs {
+    pub bottom: LitStr,
+    pub top: LitStr,
+}
+
+impl Inputs for StackInputs {
+    const EXPECTED: &'static str = "expected two short keys, the bottom and the top shape";
+
+    fn from_literals(literals: Vec<Lit>) -> Option<Self> {
+        match <[Lit; 2]>::try_from(literals).ok()? {
+            [Lit::Str(bottom), Lit::Str(top)] => Some(Self { bottom, top }),
+            _ => None,
+        }
+    }
+}
+
+/// The inputs of the painters, a short key and a color like `'r'`.
+pub(crate) struct PaintInputs {
+    pub key: LitStr,
+    pub color: LitChar,
+}
+
+impl Inputs for PaintInputs {
+    const EXPECTED: &'static str = "expected a short key and a color like 'r'";
+
+    fn from_literals(literals: Vec<Lit>) -> Option<Self> {
+        match <[Lit; 2]>::try_from(literals).ok()? {
+            [Lit::Str(key), Lit::Char(color)] => Some(Self { key, color }),
+            _ => None,
+        }
+    }
+}
+
+/// The inputs of the quad painter, a short key and a color for each quad like `"rg-b"`.
+pub(crate) struct PaintQuadsInputs {
+    pub key: LitStr,
+    pub colors: LitStr,
+}
+
+impl Inputs for PaintQuadsInputs {
+    const EXPECTED: &'static str = "expected a short key and a color for each quad like \"rg-b\"";
+
+    fn from_literals(literals: Vec<Lit>) -> Option<Self> {
+        match <[Lit; 2]>::try_from(literals).ok()? {
+            [Lit::Str(key), Lit::Str(colors)] => Some(Self { key, colors }),
+            _ => None,
+        }
+    }
+}
+
+/// The inputs of the mixer, two colors like `'r'`.
+pub(crate) struct MixInputs {
+    pub a: LitChar,
+    pub b: LitChar,
+}
+
+impl Inputs for MixInputs {
+    const EXPECTED: &'static str = "expected two colors like 'r'";
+    // A mix of two colors only refers to the color type
+    const OPTIONS: &'static [&'static str] = &["crate", "color"];
+
+    fn from_literals(literals: Vec<Lit>) -> Option<Self> {
+        match <[Lit; 2]>::try_from(literals).ok()? {
+            [Lit::Char(a), Lit::Char(b)] => Some(Self { a, b }),
+            _ => None,
+        }
+    }
+}
+
+/// The user-provided replacements for the paths of the generated tokens.
+#[derive(Default)]
+pub(crate) struct TypeOverrides {
+    krate: Option<syn::Path>,
+    shape: Option<Ident>,
+    quad: Option<Ident>,
+    subshape: Option<Ident>,
+    color: Option<Ident>,
+}
+
+impl TypeOverrides {
+    /// Resolves the paths, falling back to the given crate and type names.
+    pub(crate) fn paths(
+        &self,
+        krate: proc_macro2::TokenStream,
+        shape: &str,
+        quad: &str,
+        subshape: &str,
+    ) -> Paths {
+        let mut paths = Paths::new(krate, shape, quad, subshape);
+        if let Some(krate) = &self.krate {
+            paths.krate = krate.to_token_stream();
+        }
+        if let Some(shape) = &self.shape {
+            paths.shape = shape.clone();
+        }
+        if let Some(quad) = &self.quad {
+            paths.quad = quad.clone();
+        }
+        if let Some(subshape) = &self.subshape {
+            paths.subshape = subshape.clone();
+        }
+        if let Some(color) = &self.color {
+            paths.color = color.clone();
+        }
+
+        paths
+    }
+}
+
+pub(crate) fn set_once<T>(slot: &mut Option<T>, value: T, name: &Ident) -> syn::Result<()> {
+    if slot.replace(value).is_some() {
+        return Err(syn::Error::new(
+            name.span(),
+            format!("`{name}` is set twice"),
+        ));
+    }
+
+    Ok(())
+}
+
+/// The options every macro accepts next to its inputs.
+#[derive(Default)]
+pub(crate) struct Options {
+    /// The name of every option that was set, to reject the ones a macro does not support.
+    names: Vec<Ident>,
+    max_layers: Option<usize>,
+    strict: Option<Ident>,
+    types: TypeOverrides,
+}
+
+impl Options {
+    /// Returns whether the input continues with an option rather than an input.
+    pub(crate) fn peek(input: ParseStream) -> bool {
+        let fork = input.fork();
+        match fork.call(Ident::parse_any) {
+            Ok(name) if name == "strict" => fork.is_empty() || fork.peek(Token![,]),
+            Ok(_) => fork.peek(Token![=]),
+            Err(_) => false,
+        }
+    }
+
+    /// Parses a single `name = value` option, or the `strict` flag.
+    pub(crate) fn parse_option(&mut self, input: ParseStream) -> syn::Result<()> {
+        // `crate` is a keyword, so it needs to be parsed as any identifier
+        let name = input.call(Ident::parse_any)?;
+        self.names.push(name.clone());
+        match name.to_string().as_str() {
+            "strict" => set_once(&mut self.strict, name.clone(), &name)?,
+            "max_layers" => {
+                input.parse::<Token![=]>()?;
+                let lit = input.parse::<LitInt>()?;
+                let value = lit.base10_parse::<usize>()?;
+                if value == 0 {
+                    return Err(syn::Error::new_spanned(
+                        lit,
+                        "`max_layers` must be at least 1",
+                    ));
+                }
+                set_once(&mut self.max_layers, value, &name)?;
+            }
+            "crate" => {
+                input.parse::<Token![=]>()?;
+                let path = input.call(syn::Path::parse_mod_style)?;
+                set_once(&mut self.types.krate, path, &name)?;
+            }
+            "shape" | "quad" | "subshape" | "color" => {
+                input.parse::<Token![=]>()?;
+                let ident = input.parse::<Ident>()?;
+                let slot = match name.to_string().as_str() {
+                    "shape" => &mut self.types.shape,
+                    "quad" => &mut self.types.quad,
+                    "subshape" => &mut self.types.subshape,
+                    _ => &mut self.types.color,
+                };
+                set_once(slot, ident, &name)?;
+            }
+            _ => {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!(
+                        "unknown option `{name}` (expected one of `strict`, `max_layers`, `crate`, `shape`, `quad`, `subshape` or `color`)"
+                    ),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Attaches the options to the parsed inputs, rejecting the options not in `supported`.
+    pub(crate) fn finish<T>(self, inputs: T, supported: &[&str]) -> syn::Result<ShapeArgs<T>> {
+        let unsupported = self.names.iter().find(|name| {
+            let name = name.to_string();
+            !supported.contains(&name.as_str())
+        });
+        if let Some(name) = unsupported {
+            return Err(syn::Error::new(
+                name.span(),
+                format!(
+                    "option `{name}` is not supported by this macro (expected {})",
+                    one_of(supported)
+                ),
+            ));
+        }
+
+        // Strict keys have to be buildable, so the stacker decides on the layer limit
+        if let (Some(strict), Some(_)) = (&self.strict, self.max_layers) {
+            return Err(syn::Error::new(
+                strict.span(),
+                format!(
+                    "`strict` keys are limited to the {MAX_LAYERS} layers of the stacker, `max_layers` can't be set"
+                ),
+            ));
+        }
+
+        Ok(ShapeArgs {
+            inputs,
+            max_layers: self.max_layers.unwrap_or(MAX_LAYERS),
+            strict: self.strict.is_some(),
+            types: self.types,
+        })
+    }
+}
+
+impl<T: Inputs> Parse for ShapeArgs<T> {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut literals = vec![];
+        let mut options = Options::default();
+
+        while !input.is_empty() {
+            if input.peek(Lit) {
+                literals.push(input.parse::<Lit>()?);
+            } else {
+                options.parse_option(input)?;
+            }
+
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        let inputs = T::from_literals(literals)
+            .ok_or_else(|| syn::Error::new(Span::call_site(), T::EXPECTED))?;
+        options.finish(inputs, T::OPTIONS)
+    }
+}
+
+/// Lists the names for error messages, like "one of `a`, `b` or `c`".
+fn one_of(names: &[&str]) -> String {
+    let names = names
+        .iter()
+        .map(|name| format!("`{name}`"))
+        .collect::<Vec<_>>();
+    match names.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("one of {} or {last}", rest.join(", ")),
+        None => "no options".to_string(),
+    }
+}
@@ -0,0 +1,61 @@
+use crate::errors::literal_error;
+use shapez_core::{Color, QUADS_AMOUNT};
+use syn::{LitChar, LitStr};
+
+/// Validates a color literal like `'r'`, with the same characters as a short key.
+pub(crate) fn validate_color(input: &LitChar) -> syn::Result<Color> {
+    let color = input.value();
+    Color::from_short_key(color).ok_or_else(|| {
+        syn::Error::new_spanned(
+            input,
+            format!(
+                "'{color}' is not a valid color (expected one of {})",
+                Color::SHORT_KEYS
+            ),
+        )
+    })
+}
+
+/// Validates a color for each quad like `"rg-b"`, where `-` keeps the color of the quad.
+pub(crate) fn validate_quad_colors(input: &LitStr) -> syn::Result<[Option<Color>; QUADS_AMOUNT]> {
+    let value = input.value();
+
+    // Ensure there is a color for each quad
+    let count = value.chars().count();
+    if count != QUADS_AMOUNT {
+        return Err(syn::Error::new_spanned(
+            input,
+            format!("expected {QUADS_AMOUNT} colors, one for each quad, found {count}"),
+        ));
+    }
+
+    // Check every color, reporting every invalid one
+    let mut colors = [None; QUADS_AMOUNT];
+    let mut errors = Vec::<syn::Error>::new();
+    for (quad, (offset, color)) in value.char_indices().enumerate() {
+        if color == '-' {
+            continue;
+        }
+        match Color::from_short_key(color) {
+            Some(color) => colors[quad] = Some(color),
+            None => errors.push(literal_error(
+                input,
+                offset..offset + color.len_utf8(),
+                format!(
+                    "quad {}: '{color}' is not a valid color (expected one of {} or -)",
+                    quad + 1,
+                    Color::SHORT_KEYS
+                ),
+            )),
+        }
+    }
+
+    let mut errors = errors.into_iter();
+    match errors.next() {
+        Some(mut combined) => {
+            combined.extend(errors);
+            Err(combined)
+        }
+        None => Ok(colors),
+    }
+}
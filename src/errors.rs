@@ -0,0 +1,70 @@
+use proc_macro2::Span;
+use quote::quote;
+use shapez_core::ShapeKeyError;
+use std::fmt::Display;
+use std::ops::Range;
+use syn::LitStr;
+
+fn literal_subspan(input: &LitStr, range: Range<usize>) -> Option<Span> {
+    let token = input.token();
+    let text = token.to_string();
+    let value = input.value();
+
+    // Byte offsets only map onto the source when the literal contains no escapes
+    let offset = text.find('"')? + 1;
+    if text.get(offset..offset + value.len())? != value {
+        return None;
+    }
+
+    token.subspan(offset + range.start..offset + range.end)
+}
+
+/// Points `message` at the given byte range of a string literal,
+/// falling back to the whole literal and the column when it can not be pointed at.
+pub(crate) fn literal_error(
+    input: &LitStr,
+    range: Range<usize>,
+    message: impl Display,
+) -> syn::Error {
+    let value = input.value();
+    match literal_subspan(input, range.clone()) {
+        Some(span) => syn::Error::new(span, message),
+        None => syn::Error::new_spanned(
+            input,
+            format!(
+                "{message} (at column {} of the string)",
+                value[..range.start].chars().count() + 1
+            ),
+        ),
+    }
+}
+
+pub(crate) fn key_error(input: &LitStr, err: &ShapeKeyError) -> syn::Error {
+    literal_error(input, err.span(&input.value()), err)
+}
+
+pub(crate) fn key_errors(input: &LitStr, errors: &[ShapeKeyError]) -> syn::Error {
+    let mut errors = errors.iter().map(|err| key_error(input, err));
+    let mut combined = errors.next().expect("at least one key error");
+    combined.extend(errors);
+    combined
+}
+
+/// Combines two results, keeping the errors of both.
+pub(crate) fn join<A, B>(a: syn::Result<A>, b: syn::Result<B>) -> syn::Result<(A, B)> {
+    match (a, b) {
+        (Ok(a), Ok(b)) => Ok((a, b)),
+        (Err(mut err), Err(other)) => {
+            err.extend(other);
+            Err(err)
+        }
+        (Err(err), _) | (_, Err(err)) => Err(err),
+    }
+}
+
+/// Turns an error into tokens usable in expression position,
+/// as several `compile_error!`s are only valid there inside a block.
+pub(crate) fn compile_errors(err: syn::Error) -> proc_macro2::TokenStream {
+    let errors = err.to_compile_error();
+    quote! { { #errors } }
+}
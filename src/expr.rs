@@ -0,0 +1,277 @@
+use crate::args::{ALL_OPTIONS, Options, ShapeArgs};
+use crate::colors::{validate_color, validate_quad_colors};
+use crate::errors::join;
+use crate::validate_key;
+use proc_macro2::Span;
+use shapez_core::{QUADS_AMOUNT, Shape};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Ident, LitChar, LitInt, LitStr, Token, parenthesized};
+
+const OPERATIONS: &str = "`rotate_cw`, `rotate_ccw`, `rotate_180`, `cut_west`, `cut_east`, `quad_cut`, `destroy_half`, `stack`, `paint`, `paint_top` or `paint_quads`";
+
+/// A shape expression, either a short key or an operation on other expressions.
+///
+/// ```ignore
+/// stack(rotate_cw("CuCuCuCu"), paint("RuRuRuRu", r))
+/// ```
+pub(crate) enum Expr {
+    Key(LitStr),
+    Operation(Ident, Box<Operation>),
+}
+
+/// An operation of a shape expression, with its validated arguments.
+pub(crate) enum Operation {
+    RotateCw(Expr),
+    RotateCcw(Expr),
+    Rotate180(Expr),
+    CutWest(Expr),
+    CutEast(Expr),
+    QuadCut(Expr, usize),
+    DestroyHalf(Expr),
+    Stack(Expr, Expr),
+    Paint(Expr, LitChar),
+    PaintTop(Expr, LitChar),
+    PaintQuads(Expr, LitStr),
+}
+
+/// A single argument of an operation, before it is checked against the operation.
+enum Arg {
+    Expr(Expr),
+    Word(Ident),
+    Char(LitChar),
+    Int(LitInt),
+}
+
+impl Arg {
+    fn span(&self) -> Span {
+        match self {
+            Self::Expr(Expr::Key(key)) => key.span(),
+            Self::Expr(Expr::Operation(name, _)) => name.span(),
+            Self::Word(word) => word.span(),
+            Self::Char(lit) => lit.span(),
+            Self::Int(lit) => lit.span(),
+        }
+    }
+
+    fn into_shape(self) -> syn::Result<Expr> {
+        match self {
+            Self::Expr(expr) => Ok(expr),
+            other => Err(syn::Error::new(
+                other.span(),
+                "expected a short key or an operation",
+            )),
+        }
+    }
+
+    /// Colors can be written as `r` or as `'r'`, both are validated later on.
+    fn into_color(self) -> syn::Result<LitChar> {
+        match self {
+            Self::Char(lit) => return Ok(lit),
+            Self::Word(ref word) => {
+                let word = word.to_string();
+                let mut chars = word.chars();
+                if let (Some(color), None) = (chars.next(), chars.next()) {
+                    return Ok(LitChar::new(color, self.span()));
+                }
+            }
+            _ => {}
+        }
+
+        Err(syn::Error::new(self.span(), "expected a color like `r`"))
+    }
+
+    fn into_quad_colors(self) -> syn::Result<LitStr> {
+        match self {
+            Self::Expr(Expr::Key(lit)) => Ok(lit),
+            other => Err(syn::Error::new(
+                other.span(),
+                "expected a color for each quad like \"rg-b\"",
+            )),
+        }
+    }
+
+    fn into_quad_index(self) -> syn::Result<usize> {
+        if let Self::Int(lit) = &self {
+            let index = lit.base10_parse::<usize>()?;
+            if (1..=QUADS_AMOUNT).contains(&index) {
+                return Ok(index - 1);
+            }
+        }
+
+        Err(syn::Error::new(
+            self.span(),
+            format!("expected a quad number from 1 to {QUADS_AMOUNT}"),
+        ))
+    }
+}
+
+impl Parse for Arg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Ident) && !input.peek2(syn::token::Paren) {
+            Ok(Self::Word(input.parse()?))
+        } else if input.peek(LitChar) {
+            Ok(Self::Char(input.parse()?))
+        } else if input.peek(LitInt) {
+            Ok(Self::Int(input.parse()?))
+        } else {
+            Ok(Self::Expr(input.parse()?))
+        }
+    }
+}
+
+/// Ensures an operation got exactly `N` arguments.
+fn arguments<const N: usize>(
+    name: &Ident,
+    args: Punctuated<Arg, Token![,]>,
+) -> syn::Result<[Arg; N]> {
+    let count = args.len();
+    <[Arg; N]>::try_from(args.into_iter().collect::<Vec<_>>()).map_err(|_| {
+        let plural = if N == 1 { "" } else { "s" };
+        syn::Error::new(
+            name.span(),
+            format!("`{name}` takes {N} argument{plural}, found {count}"),
+        )
+    })
+}
+
+impl Parse for Expr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            return Ok(Self::Key(input.parse()?));
+        }
+        if !input.peek(Ident) {
+            return Err(input.error("expected a short key or an operation"));
+        }
+
+        let name = input.parse::<Ident>()?;
+        if !input.peek(syn::token::Paren) {
+            return Err(syn::Error::new(
+                name.span(),
+                format!("expected a short key or an operation like `{name}(\"CuCuCuCu\")`"),
+            ));
+        }
+        let content;
+        parenthesized!(content in input);
+        let args = Punctuated::<Arg, Token![,]>::parse_terminated(&content)?;
+
+        let operation = match name.to_string().as_str() {
+            "rotate_cw" | "rotate_ccw" | "rotate_180" | "cut_west" | "cut_east"
+            | "destroy_half" => {
+                let [shape] = arguments(&name, args)?;
+                let shape = shape.into_shape()?;
+                match name.to_string().as_str() {
+                    "rotate_cw" => Operation::RotateCw(shape),
+                    "rotate_ccw" => Operation::RotateCcw(shape),
+                    "rotate_180" => Operation::Rotate180(shape),
+                    "cut_west" => Operation::CutWest(shape),
+                    "cut_east" => Operation::CutEast(shape),
+                    _ => Operation::DestroyHalf(shape),
+                }
+            }
+            "quad_cut" => {
+                let [shape, index] = arguments(&name, args)?;
+                Operation::QuadCut(shape.into_shape()?, index.into_quad_index()?)
+            }
+            "stack" => {
+                let [bottom, top] = arguments(&name, args)?;
+                Operation::Stack(bottom.into_shape()?, top.into_shape()?)
+            }
+            "paint" | "paint_top" => {
+                let [shape, color] = arguments(&name, args)?;
+                let (shape, color) = (shape.into_shape()?, color.into_color()?);
+                match name.to_string().as_str() {
+                    "paint" => Operation::Paint(shape, color),
+                    _ => Operation::PaintTop(shape, color),
+                }
+            }
+            "paint_quads" => {
+                let [shape, colors] = arguments(&name, args)?;
+                Operation::PaintQuads(shape.into_shape()?, colors.into_quad_colors()?)
+            }
+            _ => {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("unknown operation `{name}` (expected one of {OPERATIONS})"),
+                ));
+            }
+        };
+
+        Ok(Self::Operation(name, Box::new(operation)))
+    }
+}
+
+impl Expr {
+    /// Evaluates the expression, collecting the errors of every sub-expression.
+    ///
+    /// Keys can not be empty, so an empty shape is pointed at the operation that emptied it.
+    pub(crate) fn evaluate(&self, args: &ShapeArgs<Expr>) -> syn::Result<Shape> {
+        let (name, operation) = match self {
+            Self::Key(key) => return validate_key(key, args),
+            Self::Operation(name, operation) => (name, operation),
+        };
+
+        let shape = operation.apply(args)?;
+        if shape.is_empty() {
+            return Err(syn::Error::new(
+                name.span(),
+                format!("`{name}` leaves nothing of the shape"),
+            ));
+        }
+        Ok(shape)
+    }
+}
+
+impl Operation {
+    fn apply(&self, args: &ShapeArgs<Expr>) -> syn::Result<Shape> {
+        match self {
+            Operation::RotateCw(shape) => Ok(shape.evaluate(args)?.rotate_cw()),
+            Operation::RotateCcw(shape) => Ok(shape.evaluate(args)?.rotate_ccw()),
+            Operation::Rotate180(shape) => Ok(shape.evaluate(args)?.rotate_180()),
+            Operation::CutWest(shape) => Ok(shape.evaluate(args)?.cut().0),
+            Operation::CutEast(shape) => Ok(shape.evaluate(args)?.cut().1),
+            Operation::QuadCut(shape, index) => {
+                Ok(shape.evaluate(args)?.quad_cut()[*index].clone())
+            }
+            Operation::DestroyHalf(shape) => Ok(shape.evaluate(args)?.destroy_half()),
+            Operation::Stack(bottom, top) => join(bottom.evaluate(args), top.evaluate(args))
+                .map(|(bottom, top)| bottom.stack_with_max_layers(&top, args.max_layers)),
+            Operation::Paint(shape, color) => join(shape.evaluate(args), validate_color(color))
+                .map(|(shape, color)| shape.paint(color)),
+            Operation::PaintTop(shape, color) => join(shape.evaluate(args), validate_color(color))
+                .map(|(shape, color)| shape.paint_top(color)),
+            Operation::PaintQuads(shape, colors) => {
+                join(shape.evaluate(args), validate_quad_colors(colors))
+                    .map(|(shape, colors)| shape.paint_quads(colors))
+            }
+        }
+    }
+}
+
+/// A single shape expression, optionally surrounded by options.
+impl Parse for ShapeArgs<Expr> {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut expr = None;
+        let mut options = Options::default();
+
+        while !input.is_empty() {
+            if Options::peek(input) {
+                options.parse_option(input)?;
+            } else {
+                let span = input.span();
+                if expr.replace(input.parse::<Expr>()?).is_some() {
+                    return Err(syn::Error::new(span, "only one expression is allowed"));
+                }
+            }
+
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        let expr =
+            expr.ok_or_else(|| syn::Error::new(Span::call_site(), "expected a shape expression"))?;
+        options.finish(expr, ALL_OPTIONS)
+    }
+}
@@ -1,103 +1,75 @@
 extern crate proc_macro;
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, LitStr};
+use syn::parse::ParseStream;
+use syn::{Attribute, LitStr, parse_macro_input};
 
-const MAX_LAYERS: usize = 4;
-const QUADS_AMOUNT: usize = 4;
+mod args;
+mod colors;
+mod errors;
+mod expr;
+mod shapez2;
+mod symmetry;
+mod tokens;
 
-macro_rules! error {
-    ($input:expr, $msg:expr) => {
-        syn::Error::new_spanned($input, $msg)
-            .to_compile_error()
-            .into()
-    };
-}
-
-fn get_sub_shape(input: &LitStr, sub_shape: &u8) -> proc_macro2::TokenStream {
-    // Ensure the sub-shape is valid
-    match sub_shape {
-        b'C' => quote! { Subshape::Circle },
-        b'S' => quote! { Subshape::Square },
-        b'R' => quote! { Subshape::Rectangle },
-        b'W' => quote! { Subshape::Windmill },
-        _ => error!(input, "Invalid sub-shape"),
-    }
-}
+use args::{
+    DocInputs, MixInputs, PaintInputs, PaintQuadsInputs, RecipeInputs, ShapeArgs, Shapez2Inputs,
+    StackInputs,
+};
+use errors::{compile_errors, join, key_errors};
+use shapez_core::Shape;
+use shapez_core::render::{self, RenderOptions};
+use symmetry::Symmetry;
+use tokens::Paths;
 
-fn get_color(input: &LitStr, color: &u8) -> proc_macro2::TokenStream {
-    // Ensure the color is valid
-    match color {
-        b'r' => quote! { Color::Red },
-        b'g' => quote! { Color::Green },
-        b'b' => quote! { Color::Blue },
-        b'y' => quote! { Color::Yellow },
-        b'p' => quote! { Color::Purple },
-        b'c' => quote! { Color::Cyan },
-        b'w' => quote! { Color::White },
-        b'u' => quote! { Color::Uncolored },
-        _ => error!(input, "Invalid color"),
-    }
+/// Validates a single key, turning its problems into errors pointing into the literal.
+fn validate_key<T>(key: &LitStr, args: &ShapeArgs<T>) -> syn::Result<Shape> {
+    // The grammar is shared with `shapez_core::parse_short_key`
+    let shape = if args.strict {
+        shapez_core::validate_buildable_short_key(&key.value())
+    } else {
+        shapez_core::validate_short_key_with_max_layers(&key.value(), args.max_layers)
+    };
+    shape.map_err(|errors| key_errors(key, &errors))
 }
 
-fn check_quad(input: &LitStr, quad: &[u8]) -> Option<proc_macro2::TokenStream> {
-    // Ensure the sub-shape and color are valid
-    let sub_shape = quad[0];
-    let color = quad[1];
-
-    // Check for "--"
-    if sub_shape == b'-' && color == b'-' {
-        return None;
+/// Validates the key of `args` and expands to the tokens `expand` builds from the parsed `Shape`.
+fn expand_with(
+    args: &ShapeArgs,
+    expand: impl FnOnce(Shape, &Paths) -> proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    match validate_key(&args.inputs, args) {
+        Ok(shape) => {
+            let paths = args
+                .types
+                .paths(quote! { ::shapez_core }, "Shape", "Quad", "Subshape");
+            expand(shape, &paths)
+        }
+        Err(err) => compile_errors(err),
     }
-
-    // Check for the sub-shape
-    let sub_shape_token = get_sub_shape(&input, &sub_shape);
-    let color_token = get_color(&input, &color);
-
-    Some(quote! { Some(Quad(#sub_shape_token, #color_token)) })
 }
 
-fn check_layer(input: &LitStr, layer: &str) -> proc_macro2::TokenStream {
-    // Ensure the layer is valid
-    if layer.len() != QUADS_AMOUNT * 2 {
-        return error!(input, "Invalid layer");
-    }
-
-    // Check every quad
-    let mut none_count = 0;
-    let mut quad_tokens = Vec::with_capacity(4);
-    let quads = layer.as_bytes().chunks(2).collect::<Vec<&[u8]>>();
-    for &quad in quads.iter() {
-        match check_quad(input, quad) {
-            Some(quad_token) => quad_tokens.push(quad_token),
-            None => {
-                quad_tokens.push(quote! { None });
-                none_count += 1
-            }
+/// Expands to the `Shape` a fallible operation on the inputs of `args` resulted in.
+fn expand_result<T>(args: &ShapeArgs<T>, shape: syn::Result<Shape>) -> proc_macro2::TokenStream {
+    match shape {
+        Ok(shape) => {
+            let paths = args
+                .types
+                .paths(quote! { ::shapez_core }, "Shape", "Quad", "Subshape");
+            tokens::shape_tokens(&paths, &shape)
         }
+        Err(err) => compile_errors(err),
     }
-
-    if none_count == QUADS_AMOUNT {
-        return error!(input, "Empty layer");
-    }
-
-    quote! { [ #(#quad_tokens),* ] }
 }
 
-fn check_key(input: &LitStr, shape: &str) -> proc_macro2::TokenStream {
-    // Ensure the layer count is valid
-    let layers = shape.split(':').collect::<Vec<&str>>();
-    if layers.len() > MAX_LAYERS {
-        return error!(input, "Invalid layer count");
-    }
-
-    let mut layer_tokens = vec![];
-    for &layer in layers.iter() {
-        let layer_token = check_layer(input, layer);
-        layer_tokens.push(layer_token);
-    }
-
-    quote! { vec![ #(#layer_tokens),* ] }
+/// Validates the key of `args`, applies `operation` to it and expands to the resulting `Shape`.
+fn expand_shape(
+    args: &ShapeArgs,
+    operation: impl FnOnce(Shape) -> Shape,
+) -> proc_macro2::TokenStream {
+    expand_with(args, |shape, paths| {
+        tokens::shape_tokens(paths, &operation(shape))
+    })
 }
 
 /// Procedural macro to construct a `Shape` structure from a short-form shape key,
@@ -106,6 +78,7 @@ fn check_key(input: &LitStr, shape: &str) -> proc_macro2::TokenStream {
 /// # Syntax
 ///
 /// ```
+/// # use shapez_macro::shapez_shape;
 /// shapez_shape!("RuCw--Cw:----Ru--");
 /// ```
 ///
@@ -114,6 +87,41 @@ fn check_key(input: &LitStr, shape: &str) -> proc_macro2::TokenStream {
 /// A quad can be empty as well by using '-' for both characters.
 /// Up to 4 layers can be defined, separated by colons ':'.
 ///
+/// # Options
+///
+/// Options can be passed next to the key, separated by commas:
+/// - `strict` also rejects shapes the game can not build, like quads floating over nothing
+/// - `max_layers = N` raises or lowers the layer limit of 4, e.g. for modded games
+/// - `crate = path` points the expansion at another module than `shapez_core`
+/// - `shape = Ident`, `quad = Ident`, `subshape = Ident` and `color = Ident` rename the types
+///
+/// ```
+/// # use shapez_macro::shapez_shape;
+/// let tall = shapez_shape!("Cu------:Cu------:Cu------:Cu------:Cu------", max_layers = 5);
+/// assert_eq!(tall.layers.len(), 5);
+///
+/// // The upper circle rests on the lower one, strict mode accepts it
+/// let goal = shapez_shape!(strict, "CuCu----:--Cu----");
+/// ```
+///
+/// Custom types need the same variants, the tuple-like quad and a `layers` field:
+///
+/// ```
+/// mod my_engine {
+///     pub struct ShapeDef { pub layers: Vec<[Option<Part>; 4]> }
+///     pub struct Part(pub Kind, pub Paint);
+///     pub enum Kind { Circle, Square, Rectangle, Windmill }
+///     pub enum Paint { Red, Green, Blue, Yellow, Purple, Cyan, White, Uncolored }
+/// }
+///
+/// # use shapez_macro::shapez_shape;
+/// let shape = shapez_shape!(
+///     crate = my_engine, shape = ShapeDef, quad = Part, subshape = Kind, color = Paint,
+///     "RuCw--Cw"
+/// );
+/// assert_eq!(shape.layers.len(), 1);
+/// ```
+///
 /// # Example
 ///
 /// ```
@@ -122,9 +130,11 @@ fn check_key(input: &LitStr, shape: &str) -> proc_macro2::TokenStream {
 /// let shape = shapez_shape!("RuCrSgWw:Rr------");
 /// ```
 ///
-/// This expands to:
+/// This expands to the following, with every path fully qualified into `shapez_core`:
 ///
-/// ```ignore
+/// ```
+/// # use shapez_core::{Color, Quad, Shape, Subshape};
+/// # let shape =
 /// Shape {
 ///     layers: vec![
 ///         [
@@ -141,41 +151,787 @@ fn check_key(input: &LitStr, shape: &str) -> proc_macro2::TokenStream {
 ///         ],
 ///     ],
 /// }
+/// # ;
+/// # assert_eq!(shape, shapez_macro::shapez_shape!("RuCrSgWw:Rr------"));
 /// ```
 ///
 /// # Errors
 ///
 /// Compile-time errors are emitted if:
 /// - The key is shorter than 8 characters
-/// - The key contains more than 4 layers
+/// - The key contains more than 4 layers, or the configured `max_layers`
 /// - A layer contains more or less than 4 quads
 /// - A quad contains invalid sub-shape or color
 /// - An empty layer is passed
+/// - In `strict` mode, a quad rests on nothing and no combination of cuts and stacks holds it in place
+///
+/// Every problem of the key is reported at once, without emitting a partially built shape.
+/// Each error points at the offending character or layer of the key,
+/// e.g. "layer 2, quad 3: 'x' is not a valid color (expected one of r g b y p c w u)".
+/// Where the compiler cannot point inside the literal, the column is appended to the message instead.
 ///
 /// # Notes
 /// - Valid characters for sub-shapes are 'C', 'S', 'R', and 'W'
 /// - Valid characters for colors are 'r', 'g', 'b', 'y', 'p', 'c', 'w', and 'u'
+/// - The expansion refers to the types in `shapez_core`, which must be a dependency of the calling crate
+/// - The same grammar is available at runtime through `shapez_core::parse_short_key` and `Shape::from_str`
 ///
 /// # See Also
 /// - [shapez](https://shapez.io)
 /// - [shapez viewer](https://viewer.shapez.io/)
 #[proc_macro]
 pub fn shapez_shape(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as LitStr);
+    let args = parse_macro_input!(input as ShapeArgs);
 
-    // Ensure the input is valid
-    let short_key = input.value();
-    if short_key.len() < QUADS_AMOUNT * 2 {
-        return error!(input, "Invalid input");
-    }
+    expand_shape(&args, |shape| shape).into()
+}
 
-    // Layer by layer constructs the shape from the short key
-    let shape_tokens = check_key(&input, &short_key);
+/// Procedural macro to construct a `StaticShape` structure from a short-form shape key,
+/// usable in `const` and `static` items.
+///
+/// The key follows the same format and options as [`shapez_shape!`],
+/// but the layers are expanded to a `&'static` slice instead of a `Vec`.
+///
+/// # Example
+///
+/// ```
+/// use shapez_core::StaticShape;
+/// use shapez_macro::{shapez_shape, shapez_shape_const};
+///
+/// const GOAL: StaticShape = shapez_shape_const!("RuCw--Cw:----Ru--");
+/// static LEVELS: [StaticShape; 2] = [
+///     shapez_shape_const!("CuCuCuCu"),
+///     shapez_shape_const!("RuRuRuRu:Cu------"),
+/// ];
+///
+/// assert_eq!(GOAL, shapez_shape!("RuCw--Cw:----Ru--"));
+/// assert_eq!(LEVELS[1].layers.len(), 2);
+/// ```
+///
+/// # Errors
+///
+/// The same compile-time errors as [`shapez_shape!`] are emitted.
+#[proc_macro]
+pub fn shapez_shape_const(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as ShapeArgs);
 
-    quote! {
-        Shape {
-            layers: #shape_tokens,
+    match validate_key(&args.inputs, &args) {
+        Ok(shape) => {
+            let paths =
+                args.types
+                    .paths(quote! { ::shapez_core }, "StaticShape", "Quad", "Subshape");
+            tokens::static_shape_tokens(&paths, &shape).into()
         }
+        Err(err) => compile_errors(err).into(),
     }
+}
+
+/// Procedural macro to construct a `Shape` from a short-form shape key,
+/// rotated clockwise by one quad, like the rotator of [shapez](https://shapez.io).
+///
+/// The key follows the same format and options as [`shapez_shape!`],
+/// and is validated before being rotated.
+///
+/// # Example
+///
+/// ```
+/// use shapez_macro::{shapez_rotate_cw, shapez_shape};
+///
+/// assert_eq!(shapez_rotate_cw!("RuCw--Cr:Cu------"), shapez_shape!("CrRuCw--:--Cu----"));
+/// ```
+///
+/// # Errors
+///
+/// The same compile-time errors as [`shapez_shape!`] are emitted.
+#[proc_macro]
+pub fn shapez_rotate_cw(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as ShapeArgs);
+    expand_shape(&args, |shape| shape.rotate_cw()).into()
+}
+
+/// Procedural macro to construct a `Shape` from a short-form shape key,
+/// rotated counter-clockwise by one quad.
+///
+/// The key follows the same format and options as [`shapez_shape!`],
+/// and is validated before being rotated.
+///
+/// # Example
+///
+/// ```
+/// use shapez_macro::{shapez_rotate_ccw, shapez_shape};
+///
+/// assert_eq!(shapez_rotate_ccw!("RuCw--Cr:Cu------"), shapez_shape!("Cw--CrRu:------Cu"));
+/// ```
+///
+/// # Errors
+///
+/// The same compile-time errors as [`shapez_shape!`] are emitted.
+#[proc_macro]
+pub fn shapez_rotate_ccw(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as ShapeArgs);
+    expand_shape(&args, |shape| shape.rotate_ccw()).into()
+}
+
+/// Procedural macro to construct a `Shape` from a short-form shape key,
+/// rotated by two quads.
+///
+/// The key follows the same format and options as [`shapez_shape!`],
+/// and is validated before being rotated.
+///
+/// # Example
+///
+/// ```
+/// use shapez_macro::{shapez_rotate_180, shapez_shape};
+///
+/// assert_eq!(shapez_rotate_180!("RuCw--Cr:Cu------"), shapez_shape!("--CrRuCw:----Cu--"));
+/// ```
+///
+/// # Errors
+///
+/// The same compile-time errors as [`shapez_shape!`] are emitted.
+#[proc_macro]
+pub fn shapez_rotate_180(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as ShapeArgs);
+    expand_shape(&args, |shape| shape.rotate_180()).into()
+}
+
+/// Procedural macro to cut a short-form shape key into its west and east halves at compile time,
+/// like the cutter of [shapez](https://shapez.io).
+///
+/// The key follows the same format and options as [`shapez_shape!`].
+/// The expansion is a `(west, east)` tuple of `Shape`s, see `Shape::cut`.
+/// Layers that end up empty are dropped, so a half can have no layers at all.
+///
+/// # Example
+///
+/// ```
+/// use shapez_macro::{shapez_cut, shapez_shape};
+///
+/// let (west, east) = shapez_cut!("RuCw--Cr:Cu------");
+/// assert_eq!(west, shapez_shape!("------Cr"));
+/// assert_eq!(east, shapez_shape!("RuCw----:Cu------"));
+/// ```
+///
+/// # Errors
+///
+/// The same compile-time errors as [`shapez_shape!`] are emitted.
+#[proc_macro]
+pub fn shapez_cut(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as ShapeArgs);
+    expand_with(&args, |shape, paths| {
+        let (west, east) = shape.cut();
+        let west = tokens::shape_tokens(paths, &west);
+        let east = tokens::shape_tokens(paths, &east);
+        quote! { (#west, #east) }
+    })
+    .into()
+}
+
+/// Procedural macro to cut a short-form shape key into its four quads at compile time,
+/// like the quad cutter of [shapez](https://shapez.io).
+///
+/// The key follows the same format and options as [`shapez_shape!`].
+/// The expansion is a tuple of four `Shape`s, starting from the top right quad, see `Shape::quad_cut`.
+/// Layers that end up empty are dropped, so a quad can have no layers at all.
+///
+/// # Example
+///
+/// ```
+/// use shapez_macro::{shapez_quad_cut, shapez_shape};
+///
+/// let (first, second, third, fourth) = shapez_quad_cut!("RuCw--Cr:Cu------");
+/// assert_eq!(first, shapez_shape!("Ru------:Cu------"));
+/// assert_eq!(second, shapez_shape!("--Cw----"));
+/// assert!(third.is_empty());
+/// assert_eq!(fourth, shapez_shape!("------Cr"));
+/// ```
+///
+/// # Errors
+///
+/// The same compile-time errors as [`shapez_shape!`] are emitted.
+#[proc_macro]
+pub fn shapez_quad_cut(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as ShapeArgs);
+    expand_with(&args, |shape, paths| {
+        let quads = shape.quad_cut();
+        let quad_tokens = quads.iter().map(|quad| tokens::shape_tokens(paths, quad));
+        quote! { ( #(#quad_tokens),* ) }
+    })
     .into()
 }
+
+/// Procedural macro to destroy the west half of a short-form shape key at compile time,
+/// like the half-destroyer of [shapez](https://shapez.io).
+///
+/// The key follows the same format and options as [`shapez_shape!`].
+/// The expansion is the remaining east half, see `Shape::destroy_half`.
+///
+/// # Example
+///
+/// ```
+/// use shapez_macro::{shapez_destroy_half, shapez_shape};
+///
+/// assert_eq!(shapez_destroy_half!("RuCw--Cr:----Cu--"), shapez_shape!("RuCw----"));
+/// ```
+///
+/// # Errors
+///
+/// The same compile-time errors as [`shapez_shape!`] are emitted.
+#[proc_macro]
+pub fn shapez_destroy_half(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as ShapeArgs);
+    expand_shape(&args, |shape| shape.destroy_half()).into()
+}
+
+/// Procedural macro to construct a `Shape` from a short-form shape key,
+/// rotated into its canonical form at compile time.
+///
+/// The key follows the same format and options as [`shapez_shape!`].
+/// The expansion is the rotation with the lexicographically smallest short key,
+/// see `Shape::canonical_rotation`, so keys describing the same shape expand to the same `Shape`.
+///
+/// # Example
+///
+/// ```
+/// use shapez_macro::{shapez_canonical, shapez_shape};
+///
+/// assert_eq!(shapez_canonical!("--RuCw--:Cu------"), shapez_shape!("----RuCw:--Cu----"));
+/// assert_eq!(shapez_canonical!("RuCw----:------Cu"), shapez_canonical!("--RuCw--:Cu------"));
+/// ```
+///
+/// # Errors
+///
+/// The same compile-time errors as [`shapez_shape!`] are emitted.
+#[proc_macro]
+pub fn shapez_canonical(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as ShapeArgs);
+    expand_shape(&args, |shape| shape.canonical_rotation()).into()
+}
+
+/// Procedural macro to assert at compile time that a short-form shape key is symmetric.
+///
+/// # Syntax
+///
+/// ```
+/// # use shapez_macro::shapez_assert_symmetric;
+/// shapez_assert_symmetric!("CuCuCuCu");
+/// shapez_assert_symmetric!("CuRuCuRu", order = 2);
+/// shapez_assert_symmetric!("CuCu----", axis = horizontal);
+/// ```
+///
+/// Without `order` or `axis`, the shape has to look the same in every rotation.
+/// - `order = N` requires the shape to look the same after rotating it by a fraction `1 / N` of a turn,
+///   where `N` is 1, 2 or 4, see `Shape::is_rotationally_symmetric`
+/// - `axis = name` requires the shape to look the same when mirrored along
+///   the `vertical`, `horizontal`, `diagonal` or `anti_diagonal` axis, see `Shape::is_mirror_symmetric`
+///
+/// The key follows the same format as [`shapez_shape!`], with its `strict` and `max_layers` options.
+/// The assertion expands to nothing, so it can be used both as an item and as a statement,
+/// and the options renaming the types are rejected.
+///
+/// # Example
+///
+/// ```
+/// use shapez_macro::shapez_assert_symmetric;
+///
+/// // The goal needs no rotator, whichever way it comes out of the machine
+/// shapez_assert_symmetric!("WuWuWuWu:CrCrCrCr");
+///
+/// fn main() {
+///     shapez_assert_symmetric!("RuRu----:CuCu----", axis = horizontal);
+/// }
+/// ```
+///
+/// # Errors
+///
+/// The same compile-time errors as [`shapez_shape!`] are emitted for the key,
+/// as well as an error for every symmetry the shape does not have, e.g.
+/// "`CrCuCuCu` is not rotationally symmetric of order 4, rotated by 1 quad it is `CuCrCuCu`".
+#[proc_macro]
+pub fn shapez_assert_symmetric(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as ShapeArgs<Symmetry>);
+
+    // Errors are emitted without a surrounding block, which is not allowed as an item
+    match validate_key(&args.inputs.key, &args).and_then(|shape| args.inputs.check(&shape)) {
+        Ok(()) => TokenStream::new(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Procedural macro to stack two short-form shape keys at compile time,
+/// like the stacker of [shapez](https://shapez.io).
+///
+/// # Syntax
+///
+/// ```
+/// # use shapez_macro::shapez_stack;
+/// shapez_stack!("CuCu----", "--RuRu--");
+/// ```
+///
+/// Both keys follow the same format and options as [`shapez_shape!`].
+/// The top shape falls onto the bottom one until one of its quads rests on a quad below,
+/// and the layers above the layer limit (4 by default, see `max_layers`) are cut off.
+/// The expansion is the resulting `Shape`, see `Shape::stack`.
+///
+/// # Example
+///
+/// ```
+/// use shapez_macro::{shapez_shape, shapez_stack};
+///
+/// assert_eq!(shapez_stack!("CuCu----", "----RuRu"), shapez_shape!("CuCuRuRu"));
+/// assert_eq!(shapez_stack!("CuCu----", "--RuRu--"), shapez_shape!("CuCu----:--RuRu--"));
+/// assert_eq!(
+///     shapez_stack!("CuCuCuCu:CuCuCuCu:CuCuCuCu", "RuRuRuRu:RuRuRuRu"),
+///     shapez_shape!("CuCuCuCu:CuCuCuCu:CuCuCuCu:RuRuRuRu"),
+/// );
+/// ```
+///
+/// # Errors
+///
+/// The same compile-time errors as [`shapez_shape!`] are emitted, for both keys at once.
+#[proc_macro]
+pub fn shapez_stack(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as ShapeArgs<StackInputs>);
+    let StackInputs { bottom, top } = &args.inputs;
+
+    let shape = join(validate_key(bottom, &args), validate_key(top, &args))
+        .map(|(bottom, top)| bottom.stack_with_max_layers(&top, args.max_layers));
+    expand_result(&args, shape).into()
+}
+
+/// Procedural macro to paint the top layer of a short-form shape key at compile time.
+///
+/// # Syntax
+///
+/// ```
+/// # use shapez_macro::shapez_paint_top;
+/// shapez_paint_top!("CuCu----:--Ru--Ru", 'r');
+/// ```
+///
+/// The key follows the same format and options as [`shapez_shape!`],
+/// the color is one of the short-key colors (r, g, b, y, p, c, w, or u).
+/// Every quad of the top layer takes the color, the layers below keep theirs.
+/// The expansion is the painted `Shape`, see `Shape::paint_top`.
+///
+/// # Example
+///
+/// ```
+/// use shapez_macro::{shapez_paint_top, shapez_shape};
+///
+/// assert_eq!(shapez_paint_top!("CuCu----:--Ru--Ru", 'r'), shapez_shape!("CuCu----:--Rr--Rr"));
+/// ```
+///
+/// # Errors
+///
+/// The same compile-time errors as [`shapez_shape!`] are emitted,
+/// as well as an error for an invalid color.
+#[proc_macro]
+pub fn shapez_paint_top(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as ShapeArgs<PaintInputs>);
+    let PaintInputs { key, color } = &args.inputs;
+
+    let shape = join(validate_key(key, &args), colors::validate_color(color))
+        .map(|(shape, color)| shape.paint_top(color));
+    expand_result(&args, shape).into()
+}
+
+/// Procedural macro to paint a whole short-form shape key at compile time,
+/// like the painter of [shapez](https://shapez.io).
+///
+/// # Syntax
+///
+/// ```
+/// # use shapez_macro::shapez_paint;
+/// shapez_paint!("CuCu----:--Ru--Ru", 'r');
+/// ```
+///
+/// The key follows the same format and options as [`shapez_shape!`],
+/// the color is one of the short-key colors (r, g, b, y, p, c, w, or u).
+/// Every quad of every layer takes the color.
+/// The expansion is the painted `Shape`, see `Shape::paint`.
+///
+/// # Example
+///
+/// ```
+/// use shapez_macro::{shapez_paint, shapez_shape};
+///
+/// assert_eq!(shapez_paint!("CuCu----:--Ru--Ru", 'r'), shapez_shape!("CrCr----:--Rr--Rr"));
+/// ```
+///
+/// # Errors
+///
+/// The same compile-time errors as [`shapez_shape!`] are emitted,
+/// as well as an error for an invalid color.
+#[proc_macro]
+pub fn shapez_paint(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as ShapeArgs<PaintInputs>);
+    let PaintInputs { key, color } = &args.inputs;
+
+    let shape = join(validate_key(key, &args), colors::validate_color(color))
+        .map(|(shape, color)| shape.paint(color));
+    expand_result(&args, shape).into()
+}
+
+/// Procedural macro to paint each quad of a short-form shape key at compile time,
+/// like the quad painter of [shapez](https://shapez.io).
+///
+/// # Syntax
+///
+/// ```
+/// # use shapez_macro::shapez_paint_quads;
+/// shapez_paint_quads!("CuCuCuCu", "rg-b");
+/// ```
+///
+/// The key follows the same format and options as [`shapez_shape!`].
+/// The second string holds a color for each quad, starting from the top right one,
+/// using the short-key colors (r, g, b, y, p, c, w, or u) or '-' to keep the color of the quad.
+/// Every layer is painted the same way.
+/// The expansion is the painted `Shape`, see `Shape::paint_quads`.
+///
+/// # Example
+///
+/// ```
+/// use shapez_macro::{shapez_paint_quads, shapez_shape};
+///
+/// assert_eq!(
+///     shapez_paint_quads!("CuCuCuCu:Cu------", "rg-b"),
+///     shapez_shape!("CrCgCuCb:Cr------"),
+/// );
+/// ```
+///
+/// # Errors
+///
+/// The same compile-time errors as [`shapez_shape!`] are emitted,
+/// as well as an error for every invalid color, pointing at the color.
+#[proc_macro]
+pub fn shapez_paint_quads(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as ShapeArgs<PaintQuadsInputs>);
+    let PaintQuadsInputs { key, colors } = &args.inputs;
+
+    let shape = join(
+        validate_key(key, &args),
+        colors::validate_quad_colors(colors),
+    )
+    .map(|(shape, colors)| shape.paint_quads(colors));
+    expand_result(&args, shape).into()
+}
+
+/// Procedural macro to mix two colors at compile time,
+/// like the color mixer of [shapez](https://shapez.io).
+///
+/// # Syntax
+///
+/// ```
+/// # use shapez_macro::shapez_mix;
+/// shapez_mix!('r', 'g');
+/// ```
+///
+/// Both colors are short-key colors (r, g, b, y, p, c, w, or u).
+/// The expansion is the mixed `Color` variant, see `Color::mix`.
+/// Only the `crate` and `color` options of [`shapez_shape!`] are supported, the others are rejected.
+///
+/// # Example
+///
+/// ```
+/// use shapez_core::Color;
+/// use shapez_macro::shapez_mix;
+///
+/// assert_eq!(shapez_mix!('r', 'g'), Color::Yellow);
+/// assert_eq!(shapez_mix!('b', 'w'), Color::White);
+/// assert_eq!(shapez_mix!('c', 'u'), Color::Cyan);
+/// ```
+///
+/// # Errors
+///
+/// A compile-time error is emitted for every invalid color.
+#[proc_macro]
+pub fn shapez_mix(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as ShapeArgs<MixInputs>);
+    let MixInputs { a, b } = &args.inputs;
+
+    match join(colors::validate_color(a), colors::validate_color(b)) {
+        Ok((a, b)) => {
+            let paths = args
+                .types
+                .paths(quote! { ::shapez_core }, "Shape", "Quad", "Subshape");
+            tokens::color_tokens(&paths, a.mix(b)).into()
+        }
+        Err(err) => compile_errors(err).into(),
+    }
+}
+
+/// Procedural macro to evaluate a whole chain of shape operations at compile time.
+///
+/// # Syntax
+///
+/// ```
+/// # use shapez_macro::shapez_expr;
+/// shapez_expr!(stack(rotate_cw("CuCu----"), paint("RuRuRuRu", r)));
+/// ```
+///
+/// An expression is either a short key, following the same format as [`shapez_shape!`],
+/// or one of these operations on other expressions:
+/// - `rotate_cw(shape)`, `rotate_ccw(shape)` and `rotate_180(shape)`
+/// - `cut_west(shape)` and `cut_east(shape)`, the halves of the cutter
+/// - `quad_cut(shape, N)`, the N-th quad of the quad cutter, starting from 1 at the top right
+/// - `destroy_half(shape)`
+/// - `stack(bottom, top)`
+/// - `paint(shape, color)` and `paint_top(shape, color)`, where the color is written as `r` or `'r'`
+/// - `paint_quads(shape, "rg-b")`
+///
+/// The operations behave like the macros of the same name, like [`shapez_stack!`].
+/// The options of [`shapez_shape!`] can be passed next to the expression,
+/// `max_layers` applies to every key and stack of the expression.
+/// The expansion is the single resulting `Shape`.
+///
+/// # Example
+///
+/// ```
+/// use shapez_macro::{shapez_expr, shapez_shape};
+///
+/// assert_eq!(
+///     shapez_expr!(stack(rotate_cw("CuCu----"), paint("RuRuRuRu", r))),
+///     shapez_shape!("--CuCu--:RrRrRrRr"),
+/// );
+/// assert_eq!(
+///     shapez_expr!(stack(cut_east("CuCuCuCu"), quad_cut(paint_quads("RuRuRuRu", "rgby"), 4))),
+///     shapez_shape!("CuCu--Ry"),
+/// );
+/// ```
+///
+/// # Errors
+///
+/// Every key is validated like in [`shapez_shape!`] and every color like in [`shapez_paint!`],
+/// each error pointing at the sub-expression it comes from.
+/// Unknown operations and wrong arguments are reported at the operation or the argument.
+/// An operation leaving nothing of its shape, like `cut_west("CuCu----")`, is reported at the operation.
+#[proc_macro]
+pub fn shapez_expr(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as ShapeArgs<expr::Expr>);
+    let shape = args.inputs.evaluate(&args);
+    expand_result(&args, shape).into()
+}
+
+/// Procedural macro to find a recipe building a short-form shape key at compile time.
+///
+/// # Syntax
+///
+/// ```
+/// # use shapez_macro::shapez_recipe;
+/// shapez_recipe!("CrCr----:--RgRg--");
+/// ```
+///
+/// The key follows the same format as [`shapez_shape!`],
+/// with its `strict`, `crate`, `subshape` and `color` options.
+/// The expansion is a `Recipe`, the tree of operations building the shape
+/// from the basic shapes (`"CuCuCuCu"`, `"RuRuRuRu"`, `"SuSuSuSu"` and `"WuWuWuWu"`)
+/// with the cutter, the rotators, the stacker and the painter, see `find_recipe`.
+/// The recipe displays in the syntax of [`shapez_expr!`].
+///
+/// # Example
+///
+/// ```
+/// use shapez_macro::{shapez_recipe, shapez_shape};
+///
+/// let recipe = shapez_recipe!("CrCr----:--RgRg--");
+/// assert_eq!(recipe.evaluate(), shapez_shape!("CrCr----:--RgRg--"));
+///
+/// let recipe = shapez_recipe!("SuSuSuSu:CrCrCrCr");
+/// assert_eq!(recipe.to_string(), r#"stack("SuSuSuSu", paint("CuCuCuCu", r))"#);
+/// ```
+///
+/// # Errors
+///
+/// The same compile-time errors as [`shapez_shape!`] are emitted,
+/// as well as an explanation for shapes that can not be built, like a layer floating above nothing.
+#[proc_macro]
+pub fn shapez_recipe(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as ShapeArgs<RecipeInputs>);
+    let recipe = validate_key(&args.inputs.key, &args).and_then(|shape| {
+        shapez_core::find_recipe(&shape).map_err(|err| {
+            syn::Error::new_spanned(
+                &args.inputs.key,
+                format!("the shape can not be built: {err}"),
+            )
+        })
+    });
+
+    match recipe {
+        Ok(recipe) => {
+            let paths = args
+                .types
+                .paths(quote! { ::shapez_core }, "Recipe", "Quad", "Subshape");
+            tokens::recipe_tokens(&paths, &recipe).into()
+        }
+        Err(err) => compile_errors(err).into(),
+    }
+}
+
+/// Attribute macro to show a short-form shape key as an image in the documentation of an item.
+///
+/// # Syntax
+///
+/// ```
+/// # use shapez_macro::shapez_doc;
+/// /// The goal of the first level.
+/// #[shapez_doc("RuCw--Cw")]
+/// pub struct FirstLevel;
+/// ```
+///
+/// The shape is rendered into an inline SVG image, 128 pixels wide, see `shapez_core::render::svg`.
+/// The image is added to the documentation of the item as its own paragraph, after every doc comment,
+/// so the summary of the item stays the first line of its doc comment.
+/// The key follows the same format as [`shapez_shape!`], with its `strict` and `max_layers` options,
+/// e.g. `#[shapez_doc(strict, "RuCw--Cw")]` documents only shapes the game can build.
+///
+/// # Errors
+///
+/// The same compile-time errors as [`shapez_shape!`] are emitted, leaving the item untouched.
+#[proc_macro_attribute]
+pub fn shapez_doc(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as ShapeArgs<DocInputs>);
+    // Split off the attributes, so the image ends up after every doc comment of the item
+    let split = |input: ParseStream| {
+        let attrs = input.call(Attribute::parse_outer)?;
+        Ok((attrs, input.parse::<proc_macro2::TokenStream>()?))
+    };
+    let (attrs, item) = parse_macro_input!(item with split);
+
+    match validate_key(&args.inputs.key, &args) {
+        Ok(shape) => {
+            let options = RenderOptions {
+                size: 128,
+                ..RenderOptions::default()
+            };
+            // Empty lines around the image keep it out of the surrounding paragraphs
+            let svg = render::svg(&shape, options);
+            quote! {
+                #(#attrs)*
+                #[doc = ""]
+                #[doc = #svg]
+                #[doc = ""]
+                #item
+            }
+            .into()
+        }
+        Err(err) => {
+            let errors = err.to_compile_error();
+            quote! { #errors #(#attrs)* #item }.into()
+        }
+    }
+}
+
+/// Procedural macro to construct a `shapez2::Shape` structure from a short-form shape key,
+/// following the format used in the game [shapez 2](https://shapez2.com).
+///
+/// # Syntax
+///
+/// ```
+/// # use shapez_macro::shapez2_shape;
+/// shapez2_shape!("CuRm----:P-crP---");
+/// ```
+///
+/// Each pair of characters represents a 'Part', which is one of:
+/// - a sub-shape (C, R, S, or W) followed by a color (r, g, b, c, m, y, w, or u)
+/// - a pin, written as 'P-'
+/// - a crystal, written as 'c' followed by a color
+///
+/// A part can be empty as well by using '-' for both characters.
+/// Up to 4 layers can be defined, separated by colons ':'.
+/// The same options as in [`shapez_shape!`] are accepted, where `quad` renames the `Part` type,
+/// except `strict`, as there are no rules for what the game can build in shapez 2.
+///
+/// # Example
+///
+/// ```
+/// use shapez_core::shapez2::{Color, Part, Subshape};
+/// use shapez_macro::shapez2_shape;
+///
+/// let shape = shapez2_shape!("CuRm----:P-crP---");
+/// assert_eq!(shape.layers[0][1], Some(Part::Shape(Subshape::Square, Color::Magenta)));
+/// assert_eq!(shape.layers[1][0], Some(Part::Pin));
+/// ```
+///
+/// # Errors
+///
+/// The layer structure is validated the same way as in [`shapez_shape!`],
+/// and errors are reported the same way as well.
+///
+/// # Notes
+/// - The expansion refers to the types in `shapez_core::shapez2`
+/// - The same grammar is available at runtime through `shapez_core::shapez2::parse_short_key`
+#[proc_macro]
+pub fn shapez2_shape(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as ShapeArgs<Shapez2Inputs>);
+
+    match shapez_core::shapez2::validate_short_key_with_max_layers(
+        &args.inputs.key.value(),
+        args.max_layers,
+    ) {
+        Ok(shape) => {
+            let paths = args.types.paths(
+                quote! { ::shapez_core::shapez2 },
+                "Shape",
+                "Part",
+                "Subshape",
+            );
+            shapez2::shape_tokens(&paths, &shape).into()
+        }
+        Err(errors) => compile_errors(key_errors(&args.inputs.key, &errors)).into(),
+    }
+}
+
+/// Procedural macro to construct a hexagonal `shapez2::HexShape` structure from a short-form shape key,
+/// following the format used in the game [shapez 2](https://shapez2.com).
+///
+/// # Syntax
+///
+/// ```
+/// # use shapez_macro::shapez2_hex_shape;
+/// shapez2_hex_shape!("HuFuGu------:P-----------");
+/// ```
+///
+/// Each layer has 6 parts instead of 4, so 12 characters.
+/// Parts follow the same rules as in [`shapez2_shape!`],
+/// except that the sub-shapes are the hexagonal ones (H, F, or G).
+/// The same options as in [`shapez2_shape!`] are accepted, where `subshape` renames the `HexSubshape` type.
+///
+/// # Example
+///
+/// ```
+/// use shapez_core::shapez2::{Color, HexSubshape, Part};
+/// use shapez_macro::shapez2_hex_shape;
+///
+/// let shape = shapez2_hex_shape!("HuFgGb------:P-----------");
+/// assert_eq!(shape.layers[0][1], Some(Part::Shape(HexSubshape::Flower, Color::Green)));
+/// assert_eq!(shape.layers[1].len(), 6);
+/// ```
+///
+/// # Errors
+///
+/// Next to the errors of [`shapez2_shape!`], a compile-time error is emitted
+/// if a sub-shape of regular shapes (C, R, S, or W) is used in a hexagonal key.
+///
+/// # Notes
+/// - The expansion refers to the types in `shapez_core::shapez2`
+/// - The same grammar is available at runtime through `shapez_core::shapez2::parse_hex_short_key`
+#[proc_macro]
+pub fn shapez2_hex_shape(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as ShapeArgs<Shapez2Inputs>);
+
+    match shapez_core::shapez2::validate_hex_short_key_with_max_layers(
+        &args.inputs.key.value(),
+        args.max_layers,
+    ) {
+        Ok(shape) => {
+            let paths = args.types.paths(
+                quote! { ::shapez_core::shapez2 },
+                "Shape",
+                "Part",
+                "HexSubshape",
+            );
+            shapez2::hex_shape_tokens(&paths, &shape).into()
+        }
+        Err(errors) => compile_errors(key_errors(&args.inputs.key, &errors)).into(),
+    }
+}
@@ -0,0 +1,113 @@
+use crate::tokens::Paths;
+use quote::quote;
+use shapez_core::shapez2::{Color, HexShape, HexSubshape, Layer, Part, Shape, Subshape};
+
+fn sub_shape_tokens(paths: &Paths, sub_shape: Subshape) -> proc_macro2::TokenStream {
+    let Paths {
+        krate, subshape, ..
+    } = paths;
+    match sub_shape {
+        Subshape::Circle => quote! { #krate::#subshape::Circle },
+        Subshape::Square => quote! { #krate::#subshape::Square },
+        Subshape::Star => quote! { #krate::#subshape::Star },
+        Subshape::Diamond => quote! { #krate::#subshape::Diamond },
+    }
+}
+
+fn hex_sub_shape_tokens(paths: &Paths, sub_shape: HexSubshape) -> proc_macro2::TokenStream {
+    let Paths {
+        krate, subshape, ..
+    } = paths;
+    match sub_shape {
+        HexSubshape::Hexagon => quote! { #krate::#subshape::Hexagon },
+        HexSubshape::Flower => quote! { #krate::#subshape::Flower },
+        HexSubshape::Gear => quote! { #krate::#subshape::Gear },
+    }
+}
+
+/// Turns a sub-shape into the tokens of its variant, regular and hexagonal ones living in their own types.
+type SubShapeTokens<S> = fn(&Paths, S) -> proc_macro2::TokenStream;
+
+fn color_tokens(paths: &Paths, color: Color) -> proc_macro2::TokenStream {
+    let Paths {
+        krate,
+        color: color_type,
+        ..
+    } = paths;
+    match color {
+        Color::Red => quote! { #krate::#color_type::Red },
+        Color::Green => quote! { #krate::#color_type::Green },
+        Color::Blue => quote! { #krate::#color_type::Blue },
+        Color::Cyan => quote! { #krate::#color_type::Cyan },
+        Color::Magenta => quote! { #krate::#color_type::Magenta },
+        Color::Yellow => quote! { #krate::#color_type::Yellow },
+        Color::White => quote! { #krate::#color_type::White },
+        Color::Uncolored => quote! { #krate::#color_type::Uncolored },
+    }
+}
+
+fn part_tokens<S: Copy>(
+    paths: &Paths,
+    part: &Option<Part<S>>,
+    sub_shape_tokens: SubShapeTokens<S>,
+) -> proc_macro2::TokenStream {
+    let Paths {
+        krate,
+        quad: part_type,
+        ..
+    } = paths;
+    let part_token = match part {
+        Some(Part::Shape(sub_shape, color)) => {
+            let sub_shape_token = sub_shape_tokens(paths, *sub_shape);
+            let color_token = color_tokens(paths, *color);
+            quote! { #krate::#part_type::Shape(#sub_shape_token, #color_token) }
+        }
+        Some(Part::Pin) => quote! { #krate::#part_type::Pin },
+        Some(Part::Crystal(color)) => {
+            let color_token = color_tokens(paths, *color);
+            quote! { #krate::#part_type::Crystal(#color_token) }
+        }
+        None => return quote! { ::core::option::Option::None },
+    };
+
+    quote! { ::core::option::Option::Some(#part_token) }
+}
+
+fn layer_tokens<const PARTS: usize, S: Copy>(
+    paths: &Paths,
+    layer: &Layer<PARTS, S>,
+    sub_shape_tokens: SubShapeTokens<S>,
+) -> proc_macro2::TokenStream {
+    let part_tokens = layer
+        .iter()
+        .map(|part| part_tokens(paths, part, sub_shape_tokens));
+    quote! { [ #(#part_tokens),* ] }
+}
+
+pub(crate) fn shape_tokens(paths: &Paths, shape: &Shape) -> proc_macro2::TokenStream {
+    layers_tokens(paths, &shape.layers, sub_shape_tokens)
+}
+
+pub(crate) fn hex_shape_tokens(paths: &Paths, shape: &HexShape) -> proc_macro2::TokenStream {
+    layers_tokens(paths, &shape.layers, hex_sub_shape_tokens)
+}
+
+fn layers_tokens<const PARTS: usize, S: Copy>(
+    paths: &Paths,
+    layers: &[Layer<PARTS, S>],
+    sub_shape_tokens: SubShapeTokens<S>,
+) -> proc_macro2::TokenStream {
+    let Paths {
+        krate,
+        shape: shape_type,
+        ..
+    } = paths;
+    let layer_tokens = layers
+        .iter()
+        .map(|layer| layer_tokens(paths, layer, sub_shape_tokens));
+    quote! {
+        #krate::#shape_type {
+            layers: ::std::vec![ #(#layer_tokens),* ],
+        }
+    }
+}
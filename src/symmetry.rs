@@ -0,0 +1,145 @@
+use crate::args::{Options, ShapeArgs, set_once};
+use proc_macro2::Span;
+use shapez_core::{Axis, QUADS_AMOUNT, Quad, Shape, Subshape};
+use syn::parse::{Parse, ParseStream};
+use syn::{Ident, LitInt, LitStr, Token};
+
+const AXES: &str = "`vertical`, `horizontal`, `diagonal` or `anti_diagonal`";
+
+/// The inputs of the symmetry assertion, a short key and the symmetries it has to have.
+///
+/// ```ignore
+/// shapez_assert_symmetric!("CuCuCuCu")
+/// shapez_assert_symmetric!("CuRuCuRu", order = 2)
+/// shapez_assert_symmetric!("CuCu----", axis = horizontal)
+/// ```
+pub(crate) struct Symmetry {
+    pub key: LitStr,
+    order: Option<usize>,
+    axis: Option<Axis>,
+}
+
+impl Symmetry {
+    /// Checks every requested symmetry of `shape`, rotational symmetry of order 4 by default.
+    pub(crate) fn check(&self, shape: &Shape) -> syn::Result<()> {
+        let key = shape.to_short_key();
+        let order = match (self.order, self.axis) {
+            (None, None) => Some(QUADS_AMOUNT),
+            (order, _) => order,
+        };
+
+        let mut errors = vec![];
+        if let Some(order) = order.filter(|&order| !shape.is_rotationally_symmetric(order)) {
+            let steps = QUADS_AMOUNT / order;
+            let rotated = (0..steps).fold(shape.clone(), |shape, _| shape.rotate_cw());
+            let plural = if steps == 1 { "" } else { "s" };
+            errors.push(format!(
+                "`{key}` is not rotationally symmetric of order {order}, rotated by {steps} quad{plural} it is `{rotated}`"
+            ));
+        }
+        if let Some(axis) = self.axis.filter(|&axis| !shape.is_mirror_symmetric(axis)) {
+            let windmills = shape
+                .layers
+                .iter()
+                .flatten()
+                .any(|quad| matches!(quad, Some(Quad(Subshape::Windmill, _))));
+            let hint = if windmills {
+                " (windmills have no mirror image)"
+            } else {
+                ""
+            };
+            errors.push(format!(
+                "`{key}` is not mirror symmetric along the {} axis{hint}",
+                axis_name(axis)
+            ));
+        }
+
+        errors
+            .into_iter()
+            .map(|message| syn::Error::new(self.key.span(), message))
+            .reduce(|mut all, err| {
+                all.combine(err);
+                all
+            })
+            .map_or(Ok(()), Err)
+    }
+}
+
+fn axis_name(axis: Axis) -> &'static str {
+    match axis {
+        Axis::Vertical => "vertical",
+        Axis::Horizontal => "horizontal",
+        Axis::Diagonal => "diagonal",
+        Axis::AntiDiagonal => "anti-diagonal",
+    }
+}
+
+fn parse_axis(name: &Ident) -> syn::Result<Axis> {
+    match name.to_string().as_str() {
+        "vertical" => Ok(Axis::Vertical),
+        "horizontal" => Ok(Axis::Horizontal),
+        "diagonal" => Ok(Axis::Diagonal),
+        "anti_diagonal" => Ok(Axis::AntiDiagonal),
+        _ => Err(syn::Error::new(
+            name.span(),
+            format!("unknown axis `{name}` (expected one of {AXES})"),
+        )),
+    }
+}
+
+fn parse_order(lit: &LitInt) -> syn::Result<usize> {
+    match lit.base10_parse::<usize>()? {
+        order @ (1 | 2 | 4) => Ok(order),
+        _ => Err(syn::Error::new(
+            lit.span(),
+            "expected an order of 1, 2 or 4, quads can only be rotated by whole quads",
+        )),
+    }
+}
+
+/// Returns whether the input continues with `order = N` or `axis = name`.
+fn peek_symmetry(input: ParseStream) -> bool {
+    let fork = input.fork();
+    matches!(fork.parse::<Ident>(), Ok(name) if name == "order" || name == "axis")
+        && fork.peek(Token![=])
+}
+
+/// A short key with `order` and `axis`, optionally surrounded by the usual options.
+impl Parse for ShapeArgs<Symmetry> {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut key = None;
+        let mut order = None;
+        let mut axis = None;
+        let mut options = Options::default();
+
+        while !input.is_empty() {
+            if input.peek(LitStr) {
+                let lit = input.parse::<LitStr>()?;
+                if key.replace(lit.clone()).is_some() {
+                    return Err(syn::Error::new(lit.span(), "only one short key is allowed"));
+                }
+            } else if peek_symmetry(input) {
+                let name = input.parse::<Ident>()?;
+                input.parse::<Token![=]>()?;
+                if name == "order" {
+                    let value = parse_order(&input.parse::<LitInt>()?)?;
+                    set_once(&mut order, value, &name)?;
+                } else {
+                    let value = parse_axis(&input.parse::<Ident>()?)?;
+                    set_once(&mut axis, value, &name)?;
+                }
+            } else {
+                options.parse_option(input)?;
+            }
+
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        let key = key.ok_or_else(|| syn::Error::new(Span::call_site(), "expected a short key"))?;
+        // Nothing is expanded, so only the options of the key check apply
+        options.finish(Symmetry { key, order, axis }, &["strict", "max_layers"])
+    }
+}
@@ -0,0 +1,158 @@
+use proc_macro2::{Ident, Span};
+use quote::quote;
+use shapez_core::{Color, Layer, Quad, Recipe, Shape, Subshape};
+
+/// The paths the generated tokens point at, `::shapez_core` and its type names by default.
+pub(crate) struct Paths {
+    pub krate: proc_macro2::TokenStream,
+    pub shape: Ident,
+    pub quad: Ident,
+    pub subshape: Ident,
+    pub color: Ident,
+}
+
+impl Paths {
+    pub(crate) fn new(
+        krate: proc_macro2::TokenStream,
+        shape: &str,
+        quad: &str,
+        subshape: &str,
+    ) -> Self {
+        Self {
+            krate,
+            shape: Ident::new(shape, Span::call_site()),
+            quad: Ident::new(quad, Span::call_site()),
+            subshape: Ident::new(subshape, Span::call_site()),
+            color: Ident::new("Color", Span::call_site()),
+        }
+    }
+}
+
+fn sub_shape_tokens(paths: &Paths, sub_shape: Subshape) -> proc_macro2::TokenStream {
+    let Paths {
+        krate, subshape, ..
+    } = paths;
+    match sub_shape {
+        Subshape::Circle => quote! { #krate::#subshape::Circle },
+        Subshape::Square => quote! { #krate::#subshape::Square },
+        Subshape::Rectangle => quote! { #krate::#subshape::Rectangle },
+        Subshape::Windmill => quote! { #krate::#subshape::Windmill },
+    }
+}
+
+pub(crate) fn color_tokens(paths: &Paths, color: Color) -> proc_macro2::TokenStream {
+    let Paths {
+        krate,
+        color: color_type,
+        ..
+    } = paths;
+    match color {
+        Color::Red => quote! { #krate::#color_type::Red },
+        Color::Green => quote! { #krate::#color_type::Green },
+        Color::Blue => quote! { #krate::#color_type::Blue },
+        Color::Yellow => quote! { #krate::#color_type::Yellow },
+        Color::Purple => quote! { #krate::#color_type::Purple },
+        Color::Cyan => quote! { #krate::#color_type::Cyan },
+        Color::White => quote! { #krate::#color_type::White },
+        Color::Uncolored => quote! { #krate::#color_type::Uncolored },
+    }
+}
+
+fn quad_tokens(paths: &Paths, quad: &Option<Quad>) -> proc_macro2::TokenStream {
+    let Paths {
+        krate,
+        quad: quad_type,
+        ..
+    } = paths;
+    match quad {
+        Some(Quad(sub_shape, color)) => {
+            let sub_shape_token = sub_shape_tokens(paths, *sub_shape);
+            let color_token = color_tokens(paths, *color);
+            quote! {
+                ::core::option::Option::Some(#krate::#quad_type(#sub_shape_token, #color_token))
+            }
+        }
+        None => quote! { ::core::option::Option::None },
+    }
+}
+
+fn layer_tokens(paths: &Paths, layer: &Layer) -> proc_macro2::TokenStream {
+    let quad_tokens = layer.iter().map(|quad| quad_tokens(paths, quad));
+    quote! { [ #(#quad_tokens),* ] }
+}
+
+pub(crate) fn shape_tokens(paths: &Paths, shape: &Shape) -> proc_macro2::TokenStream {
+    let Paths {
+        krate,
+        shape: shape_type,
+        ..
+    } = paths;
+    let layer_tokens = shape.layers.iter().map(|layer| layer_tokens(paths, layer));
+    quote! {
+        #krate::#shape_type {
+            layers: ::std::vec![ #(#layer_tokens),* ],
+        }
+    }
+}
+
+pub(crate) fn static_shape_tokens(paths: &Paths, shape: &Shape) -> proc_macro2::TokenStream {
+    let Paths {
+        krate,
+        shape: shape_type,
+        ..
+    } = paths;
+    let layer_tokens = shape.layers.iter().map(|layer| layer_tokens(paths, layer));
+    quote! {
+        #krate::#shape_type {
+            layers: &[ #(#layer_tokens),* ],
+        }
+    }
+}
+
+/// Emits the operation tree of a recipe, with `paths.shape` naming the recipe type.
+pub(crate) fn recipe_tokens(paths: &Paths, recipe: &Recipe) -> proc_macro2::TokenStream {
+    let Paths {
+        krate,
+        shape: recipe_type,
+        ..
+    } = paths;
+    let boxed = |recipe: &Recipe| {
+        let tokens = recipe_tokens(paths, recipe);
+        quote! { ::std::boxed::Box::new(#tokens) }
+    };
+    match recipe {
+        Recipe::Extract(sub_shape) => {
+            let sub_shape = sub_shape_tokens(paths, *sub_shape);
+            quote! { #krate::#recipe_type::Extract(#sub_shape) }
+        }
+        Recipe::RotateCw(recipe) => {
+            let recipe = boxed(recipe);
+            quote! { #krate::#recipe_type::RotateCw(#recipe) }
+        }
+        Recipe::RotateCcw(recipe) => {
+            let recipe = boxed(recipe);
+            quote! { #krate::#recipe_type::RotateCcw(#recipe) }
+        }
+        Recipe::Rotate180(recipe) => {
+            let recipe = boxed(recipe);
+            quote! { #krate::#recipe_type::Rotate180(#recipe) }
+        }
+        Recipe::CutWest(recipe) => {
+            let recipe = boxed(recipe);
+            quote! { #krate::#recipe_type::CutWest(#recipe) }
+        }
+        Recipe::CutEast(recipe) => {
+            let recipe = boxed(recipe);
+            quote! { #krate::#recipe_type::CutEast(#recipe) }
+        }
+        Recipe::Stack(bottom, top) => {
+            let (bottom, top) = (boxed(bottom), boxed(top));
+            quote! { #krate::#recipe_type::Stack(#bottom, #top) }
+        }
+        Recipe::Paint(recipe, color) => {
+            let recipe = boxed(recipe);
+            let color = color_tokens(paths, *color);
+            quote! { #krate::#recipe_type::Paint(#recipe, #color) }
+        }
+    }
+}
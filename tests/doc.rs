@@ -0,0 +1,16 @@
+use documented::Documented;
+use shapez_macro::shapez_doc;
+
+/// Above the attribute.
+#[shapez_doc("RuCw--Cw:----Ru--")]
+/// Below the attribute.
+#[derive(Documented)]
+struct Level;
+
+#[test]
+fn image_follows_the_doc_comments() {
+    let (text, image) = Level::DOCS.split_once("\n\n").unwrap();
+    assert_eq!(text, "Above the attribute.\nBelow the attribute.");
+    assert!(image.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"128\""));
+    assert!(image.trim_end().ends_with("</svg>"));
+}
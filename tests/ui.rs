@@ -0,0 +1,8 @@
+/// Checks the compile-time errors of the macros against the `.stderr` files next to each case.
+///
+/// Run with `TRYBUILD=overwrite` to update the expected errors.
+#[test]
+fn ui() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
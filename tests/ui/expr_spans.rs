@@ -0,0 +1,11 @@
+use shapez_core::Shape;
+use shapez_macro::shapez_expr;
+
+fn main() {
+    // Each error points at the sub-expression it comes from
+    let _: Shape = shapez_expr!(stack(rotate_cw("CuCx----"), paint("RuRuRuRu", q)));
+    let _: Shape = shapez_expr!(quad_cut("CuCuCuCu", 5));
+    let _: Shape = shapez_expr!(rotate("CuCuCuCu"));
+    let _: Shape = shapez_expr!(rotate_cw(cut_west("CuCu----")));
+    let _: Shape = shapez_expr!(stack("CuCuCuCu", quad_cut("Cu------", 2)));
+}
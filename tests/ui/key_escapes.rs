@@ -0,0 +1,8 @@
+use shapez_core::Shape;
+use shapez_macro::shapez_shape;
+
+fn main() {
+    // The column counts the characters of the unescaped key, so `\x43` is a single column
+    // and the `x` is reported at column 8 rather than where it sits in the source
+    let _: Shape = shapez_shape!("Ru\x43w--Cx");
+}
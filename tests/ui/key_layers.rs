@@ -0,0 +1,8 @@
+use shapez_core::Shape;
+use shapez_macro::shapez_shape;
+
+fn main() {
+    let _: Shape = shapez_shape!("Cu------:Cu------:Cu------", max_layers = 2);
+    let _: Shape = shapez_shape!("Cu------:Cu---");
+    let _: Shape = shapez_shape!("Cu--");
+}
@@ -0,0 +1,7 @@
+use shapez_core::Shape;
+use shapez_macro::shapez_shape;
+
+fn main() {
+    // Every typo is reported at once, and no partial shape is emitted
+    let _: Shape = shapez_shape!("RxCu--Cw:--Qu----:--------");
+}
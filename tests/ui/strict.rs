@@ -0,0 +1,8 @@
+use shapez_core::Shape;
+use shapez_macro::{shapez_expr, shapez_shape};
+
+fn main() {
+    let _: Shape = shapez_shape!(strict, "Cu------:----Cu--");
+    let _: Shape = shapez_shape!(strict, "Cu------", max_layers = 5);
+    let _: Shape = shapez_expr!(strict, rotate_cw("Cu------:----Cu--"));
+}
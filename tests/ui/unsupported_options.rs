@@ -0,0 +1,20 @@
+use shapez_macro::{shapez_assert_symmetric, shapez_mix, shapez2_hex_shape, shapez2_shape};
+
+fn main() {
+    let _ = shapez2_shape!(strict, "P-------:Cu------");
+    let _ = shapez2_hex_shape!("HuFuGu------", strict);
+    let _ = shapez_mix!(strict, 'r', 'g');
+    let _ = shapez_mix!('r', 'g', max_layers = 5);
+    let _ = shapez_mix!(shape = MyShape, 'r', 'g');
+}
+
+shapez_assert_symmetric!(crate = nonexistent, quad = Nope, "CuCuCuCu");
+
+#[shapez_macro::shapez_doc(crate = nonexistent, "CuCuCuCu")]
+struct Documented;
+
+fn recipes() {
+    let _ = shapez_macro::shapez_recipe!("CuCuCuCu", shape = MyRecipe);
+    let _ = shapez_macro::shapez_recipe!("CuCuCuCu", quad = MyQuad);
+    let _ = shapez_macro::shapez_recipe!("CuCuCuCu", max_layers = 5);
+}